@@ -0,0 +1,197 @@
+use std::fs;
+use std::sync::OnceLock;
+
+use crate::combat::WeaponType;
+use crate::monsters::MonsterType;
+use crate::world::Terrain;
+
+/// Optional overrides for the built-in balance numbers, as `entity.field=value` lines, e.g.
+/// `ogre.max_health=40`, `sword.max_damage=12`, `snow.speed=0.6`. Lines starting with `#` are
+/// comments and anything left out keeps its default.
+const BALANCE_PATH: &str = "balance.cfg";
+
+#[derive(Clone, Copy, Debug)]
+pub struct MonsterStats {
+    pub max_health: i32,
+    pub base_damage: i32,
+    pub damage_spread: i32,
+    pub attack_cooldown: f32,
+    pub drop_chance: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct WeaponStats {
+    pub min_damage: i32,
+    pub max_damage: i32,
+    pub attack_cooldown: f32,
+}
+
+/// Every tunable number, indexed by each enum's declaration order
+struct Balance {
+    monsters: [MonsterStats; MonsterType::ALL.len()],
+    weapons: [WeaponStats; WeaponType::ALL.len()],
+    terrain_speed: [f32; Terrain::ALL.len()],
+}
+
+static BALANCE: OnceLock<Balance> = OnceLock::new();
+
+fn balance() -> &'static Balance {
+    BALANCE.get_or_init(|| {
+        let defaults = Balance::defaults();
+        match fs::read_to_string(BALANCE_PATH) {
+            Err(_) => defaults,
+            // One bad line rejects the whole file so a half-applied rebalance never ships
+            Ok(contents) => defaults.with_overrides(&contents).unwrap_or_else(|errors| {
+                eprintln!("Ignoring every override in {} and using the built-in balance:", BALANCE_PATH);
+                for error in errors {
+                    eprintln!("  {}", error);
+                }
+                Balance::defaults()
+            }),
+        }
+    })
+}
+
+pub fn monster(monster_type: MonsterType) -> MonsterStats {
+    balance().monsters[monster_type as usize]
+}
+
+pub fn weapon(kind: WeaponType) -> WeaponStats {
+    balance().weapons[kind as usize]
+}
+
+pub fn terrain_speed(terrain: Terrain) -> f32 {
+    balance().terrain_speed[terrain as usize]
+}
+
+/// Finite and above zero: NaN fails too, and an infinite cooldown would never come back around
+fn valid_cooldown(cooldown: f32) -> bool {
+    cooldown.is_finite() && cooldown > 0.0
+}
+
+/// Config-file name for an entity: lowercase with underscores, e.g. `snow_goblin`
+fn entity_key(name: &str) -> String {
+    name.to_lowercase().replace(' ', "_")
+}
+
+impl Balance {
+    fn defaults() -> Self {
+        Self {
+            monsters: MonsterType::ALL.map(|t| t.default_stats()),
+            weapons: WeaponType::ALL.map(|w| w.default_stats()),
+            terrain_speed: Terrain::ALL.map(|t| t.default_speed_modifier()),
+        }
+    }
+
+    /// Apply every line of `contents`, then check the result; errors carry their line number
+    fn with_overrides(mut self, contents: &str) -> Result<Self, Vec<String>> {
+        let mut errors = Vec::new();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Err(error) = self.apply(line) {
+                errors.push(format!("line {}: {}", number + 1, error));
+            }
+        }
+        errors.extend(self.validate());
+        if errors.is_empty() { Ok(self) } else { Err(errors) }
+    }
+
+    fn apply(&mut self, line: &str) -> Result<(), String> {
+        let (key, value) = line.split_once('=').ok_or("expected `entity.field=value`")?;
+        let (entity, field) = key.trim().split_once('.').ok_or_else(|| format!("`{}` has no field", key.trim()))?;
+        let value = value.trim();
+        let int = || value.parse::<i32>().map_err(|_| format!("`{}` is not a whole number", value));
+        let float = || value.parse::<f32>().map_err(|_| format!("`{}` is not a number", value));
+        let unknown_field = || format!("unknown field `{}` for `{}`", field, entity);
+
+        if let Some(i) = MonsterType::ALL.iter().position(|t| entity_key(t.name()) == entity) {
+            let stats = &mut self.monsters[i];
+            match field {
+                "max_health" => stats.max_health = int()?,
+                "base_damage" => stats.base_damage = int()?,
+                "damage_spread" => stats.damage_spread = int()?,
+                "attack_cooldown" => stats.attack_cooldown = float()?,
+                "drop_chance" => stats.drop_chance = float()?,
+                _ => return Err(unknown_field()),
+            }
+        } else if let Some(i) = WeaponType::ALL.iter().position(|w| entity_key(w.name()) == entity) {
+            let stats = &mut self.weapons[i];
+            match field {
+                "min_damage" => stats.min_damage = int()?,
+                "max_damage" => stats.max_damage = int()?,
+                "attack_cooldown" => stats.attack_cooldown = float()?,
+                _ => return Err(unknown_field()),
+            }
+        } else if let Some(i) = Terrain::ALL.iter().position(|t| entity_key(t.name()) == entity) {
+            match field {
+                "speed" => self.terrain_speed[i] = float()?,
+                _ => return Err(unknown_field()),
+            }
+        } else {
+            return Err(format!("unknown entity `{}`", entity));
+        }
+        Ok(())
+    }
+
+    /// Range checks on the merged values, so overrides that only make sense together are caught too
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (t, stats) in MonsterType::ALL.iter().zip(&self.monsters) {
+            let name = entity_key(t.name());
+            if stats.max_health < 1 {
+                errors.push(format!("{}.max_health must be at least 1", name));
+            }
+            if stats.base_damage < 0 || stats.damage_spread < 0 {
+                errors.push(format!("{} damage can't be negative", name));
+            }
+            if !valid_cooldown(stats.attack_cooldown) {
+                errors.push(format!("{}.attack_cooldown must be a finite number above 0", name));
+            }
+            if !(0.0..=1.0).contains(&stats.drop_chance) {
+                errors.push(format!("{}.drop_chance must be between 0 and 1", name));
+            }
+        }
+        for (w, stats) in WeaponType::ALL.iter().zip(&self.weapons) {
+            let name = entity_key(w.name());
+            if stats.min_damage < 0 || stats.min_damage > stats.max_damage {
+                errors.push(format!("{} needs 0 <= min_damage <= max_damage", name));
+            }
+            if !valid_cooldown(stats.attack_cooldown) {
+                errors.push(format!("{}.attack_cooldown must be a finite number above 0", name));
+            }
+        }
+        for (t, &speed) in Terrain::ALL.iter().zip(&self.terrain_speed) {
+            if !(speed > 0.0 && speed <= 2.0) {
+                errors.push(format!("{}.speed must be above 0 and at most 2", entity_key(t.name())));
+            }
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_override_applies() {
+        let balance = Balance::defaults().with_overrides("# tuning\nogre.max_health=40\nsword.max_damage=30").unwrap();
+        assert_eq!(balance.monsters[MonsterType::Ogre as usize].max_health, 40);
+        assert_eq!(balance.weapons[WeaponType::Sword as usize].max_damage, 30);
+    }
+
+    #[test]
+    fn unknown_key_is_rejected() {
+        assert!(Balance::defaults().with_overrides("ogre.armor=3").is_err());
+        assert!(Balance::defaults().with_overrides("dragon.max_health=3").is_err());
+    }
+
+    #[test]
+    fn non_finite_cooldowns_are_rejected() {
+        assert!(Balance::defaults().with_overrides("ogre.attack_cooldown=NaN").is_err());
+        assert!(Balance::defaults().with_overrides("sword.attack_cooldown=inf").is_err());
+    }
+}
@@ -7,6 +7,13 @@ pub struct GameCamera {
     pub x: f32,
     pub y: f32,
     pub lerp_speed: f32,
+    pub shake_time: f32,      // Remaining shake duration in seconds
+    pub shake_intensity: f32, // Peak offset in pixels at the start of the shake
+    shake_offset: (f32, f32), // Render-only offset for this frame
+    bounds: Option<(Vec2, Vec2)>, // World-space (min, max) the camera center stays inside
+    pub free_look: bool, // Detached from the follow; the view sits at the player plus the offset
+    free_look_offset: (f32, f32), // World tiles from the player
+    drag_anchor: Option<(f32, f32)>, // Screen position of the middle mouse last frame, while dragging
 }
 
 impl GameCamera {
@@ -15,13 +22,145 @@ impl GameCamera {
             x: 0.0,
             y: 0.0,
             lerp_speed: 5.0,
+            shake_time: 0.0,
+            shake_intensity: 0.0,
+            shake_offset: (0.0, 0.0),
+            bounds: None,
+            free_look: false,
+            free_look_offset: (0.0, 0.0),
+            drag_anchor: None,
         }
     }
 
+    /// Keep the camera center inside a world-space box; `None` lets it roam freely
+    #[allow(dead_code)] // Nothing bounded exists in the open world yet
+    pub fn set_bounds(&mut self, bounds: Option<(Vec2, Vec2)>) {
+        self.bounds = bounds;
+    }
+
+    const SHAKE_DURATION: f32 = 0.3;
+
+    /// Start (or strengthen) a screen shake; a weaker shake never cuts a stronger one short
+    pub fn add_shake(&mut self, intensity: f32) {
+        let current = self.shake_intensity * self.shake_falloff();
+        if intensity >= current {
+            self.shake_intensity = intensity;
+            self.shake_time = Self::SHAKE_DURATION;
+        }
+    }
+
+    /// Squared falloff so the shake eases out to exactly zero
+    fn shake_falloff(&self) -> f32 {
+        let t = (self.shake_time / Self::SHAKE_DURATION).clamp(0.0, 1.0);
+        t * t
+    }
+
+    /// Advance the shake and pick this frame's random offset
+    pub fn update_shake(&mut self, dt: f32) {
+        self.shake_time = (self.shake_time - dt).max(0.0);
+        if self.shake_time <= 0.0 {
+            self.shake_intensity = 0.0;
+            self.shake_offset = (0.0, 0.0);
+            return;
+        }
+
+        let amplitude = self.shake_intensity * self.shake_falloff();
+        let angle = rand::gen_range(0.0, std::f32::consts::TAU);
+        self.shake_offset = (angle.cos() * amplitude, angle.sin() * amplitude);
+    }
+
+    const FREE_LOOK_RANGE: f32 = 16.0; // Tiles; spawns around the player still cover the view out here
+
+    /// Middle-mouse drag pans the view away from the player, entering free-look. While it's on, the
+    /// view holds its offset from the player and this returns true; `recenter` ends it.
+    pub fn free_look(&mut self, player_x: f32, player_y: f32) -> bool {
+        let mouse = mouse_position();
+        if is_mouse_button_pressed(MouseButton::Middle) {
+            self.drag_anchor = Some(mouse);
+            if !self.free_look {
+                // Start from wherever the view is now, so grabbing it doesn't jump
+                self.free_look = true;
+                self.free_look_offset = (self.x - player_x, self.y - player_y);
+            }
+        }
+        if !is_mouse_button_down(MouseButton::Middle) {
+            self.drag_anchor = None;
+        }
+        if let Some((anchor_x, anchor_y)) = self.drag_anchor {
+            // Dragging moves the ground with the mouse, so the view moves the other way
+            let (step_x, step_y) = screen_step_to_world(anchor_x - mouse.0, anchor_y - mouse.1);
+            let range = Self::FREE_LOOK_RANGE;
+            self.free_look_offset.0 = (self.free_look_offset.0 + step_x).clamp(-range, range);
+            self.free_look_offset.1 = (self.free_look_offset.1 + step_y).clamp(-range, range);
+            self.drag_anchor = Some(mouse);
+        }
+        if !self.free_look {
+            return false;
+        }
+
+        self.x = player_x + self.free_look_offset.0;
+        self.y = player_y + self.free_look_offset.1;
+        if let Some((min, max)) = self.bounds {
+            self.x = self.x.clamp(min.x, max.x.max(min.x));
+            self.y = self.y.clamp(min.y, max.y.max(min.y));
+        }
+        true
+    }
+
+    /// Leave free-look; the follow lerp then eases the view back onto the player
+    pub fn recenter(&mut self) {
+        self.free_look = false;
+        self.free_look_offset = (0.0, 0.0);
+        self.drag_anchor = None;
+    }
+
+    const EDGE_MARGIN: f32 = 24.0; // Pixels from the window edge that start a pan
+    const EDGE_SCROLL_SPEED: f32 = 600.0; // Screen pixels per second
+    const EDGE_SCROLL_RANGE: f32 = 12.0; // Furthest the view can pan from the player, in tiles
+
+    /// Pan toward whichever edges the mouse is touching, diagonally at corners.
+    /// Returns false when the mouse is off the edges, so the caller can go back to following.
+    pub fn edge_scroll(&mut self, player_x: f32, player_y: f32, dt: f32) -> bool {
+        let (mouse_x, mouse_y) = mouse_position();
+        let axis = |pos: f32, size: f32| {
+            if pos < Self::EDGE_MARGIN {
+                -1.0
+            } else if pos > size - Self::EDGE_MARGIN {
+                1.0
+            } else {
+                0.0
+            }
+        };
+        let (pan_x, pan_y) = (axis(mouse_x, screen_width()), axis(mouse_y, screen_height()));
+        if pan_x == 0.0 && pan_y == 0.0 {
+            return false;
+        }
+
+        let (step_x, step_y) =
+            screen_step_to_world(pan_x * Self::EDGE_SCROLL_SPEED * dt, pan_y * Self::EDGE_SCROLL_SPEED * dt);
+        self.x += step_x;
+        self.y += step_y;
+
+        let range = Self::EDGE_SCROLL_RANGE;
+        self.x = self.x.clamp(player_x - range, player_x + range);
+        self.y = self.y.clamp(player_y - range, player_y + range);
+        if let Some((min, max)) = self.bounds {
+            self.x = self.x.clamp(min.x, max.x.max(min.x));
+            self.y = self.y.clamp(min.y, max.y.max(min.y));
+        }
+        true
+    }
+
     pub fn follow(&mut self, target_x: f32, target_y: f32, dt: f32) {
         let lerp = 1.0 - (-self.lerp_speed * dt).exp();
         self.x += (target_x - self.x) * lerp;
         self.y += (target_y - self.y) * lerp;
+
+        // Clamp after the lerp so the camera eases up against an edge instead of snapping to it
+        if let Some((min, max)) = self.bounds {
+            self.x = self.x.clamp(min.x, max.x.max(min.x));
+            self.y = self.y.clamp(min.y, max.y.max(min.y));
+        }
     }
 
     /// Convert world coordinates to isometric screen coordinates
@@ -33,14 +172,20 @@ impl GameCamera {
         let iso_x = (rel_x - rel_y) * (TILE_WIDTH / 2.0);
         let iso_y = (rel_x + rel_y) * (TILE_HEIGHT / 2.0);
 
-        // Center on screen
-        let screen_x = screen_width() / 2.0 + iso_x;
-        let screen_y = screen_height() / 2.0 + iso_y;
+        // Center on screen (shake only moves what's drawn, never input mapping)
+        let screen_x = screen_width() / 2.0 + iso_x + self.shake_offset.0;
+        let screen_y = screen_height() / 2.0 + iso_y + self.shake_offset.1;
 
         (screen_x, screen_y)
     }
 
-    /// Convert screen coordinates to world coordinates
+    /// Whether a world point lands on screen, allowing `margin` pixels past each edge for sprite size
+    pub fn is_on_screen(&self, world_x: f32, world_y: f32, margin: f32) -> bool {
+        let (x, y) = self.world_to_screen(world_x, world_y);
+        x >= -margin && x <= screen_width() + margin && y >= -margin && y <= screen_height() + margin
+    }
+
+    /// Convert screen coordinates to world coordinates (ignores shake so targeting stays put)
     pub fn screen_to_world(&self, screen_x: f32, screen_y: f32) -> (f32, f32) {
         let rel_screen_x = screen_x - screen_width() / 2.0;
         let rel_screen_y = screen_y - screen_height() / 2.0;
@@ -52,3 +197,11 @@ impl GameCamera {
         (world_x + self.x, world_y + self.y)
     }
 }
+
+/// World-space size of a screen-space step: the inverse projection of screen_to_world without the centering
+fn screen_step_to_world(step_x: f32, step_y: f32) -> (f32, f32) {
+    (
+        (step_x / (TILE_WIDTH / 2.0) + step_y / (TILE_HEIGHT / 2.0)) / 2.0,
+        (step_y / (TILE_HEIGHT / 2.0) - step_x / (TILE_WIDTH / 2.0)) / 2.0,
+    )
+}
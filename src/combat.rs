@@ -1,23 +1,197 @@
-use macroquad::rand;
+use crate::balance::{self, WeaponStats};
+use crate::rng::GameRng;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum WeaponType {
     Sword,
     Axe,
     Mace,
+    Bow,
+    Staff, // The Mage's starting weapon; not in any loot table
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ArmorType {
     Leather,
     Chainmail,
     Platemail,
+    Shield,
+}
+
+/// What a hit is made of; each armor type resists these differently
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DamageType {
+    Physical,
+    Fire,
+}
+
+impl DamageType {
+    pub const ALL: [DamageType; 2] = [DamageType::Physical, DamageType::Fire];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DamageType::Physical => "Physical",
+            DamageType::Fire => "Fire",
+        }
+    }
+}
+
+/// Damage range and swing time when no weapon is equipped
+pub const FIST_DAMAGE: (i32, i32) = (1, 2);
+pub const FIST_COOLDOWN: f32 = 0.3;
+
+/// Fixed stats a unique replaces on its base type
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UniqueStats {
+    Weapon { kind: WeaponType, damage: (i32, i32) },
+    Armor { kind: ArmorType, physical: i32, fire: i32 },
+}
+
+/// A named item with fixed stats above anything found normally; only elites can drop one
+#[derive(Debug, PartialEq)]
+pub struct UniqueDef {
+    pub key: &'static str, // Stable save-file id, independent of the display name
+    pub name: &'static str,
+    pub stats: UniqueStats,
+}
+
+pub const UNIQUES: [UniqueDef; 5] = [
+    UniqueDef { key: "gravecleaver", name: "Gravecleaver", stats: UniqueStats::Weapon { kind: WeaponType::Axe, damage: (9, 13) } },
+    UniqueDef { key: "whisper", name: "Whisper of the Dunes", stats: UniqueStats::Weapon { kind: WeaponType::Sword, damage: (6, 14) } },
+    UniqueDef { key: "stonefist", name: "Stonefist", stats: UniqueStats::Weapon { kind: WeaponType::Mace, damage: (11, 11) } },
+    UniqueDef { key: "emberweave", name: "Emberweave", stats: UniqueStats::Armor { kind: ArmorType::Leather, physical: 2, fire: 4 } },
+    UniqueDef { key: "frostwall", name: "The Frostwall", stats: UniqueStats::Armor { kind: ArmorType::Platemail, physical: 6, fire: 3 } },
+];
+
+impl UniqueDef {
+    pub fn by_key(key: &str) -> Option<&'static UniqueDef> {
+        UNIQUES.iter().find(|u| u.key == key)
+    }
+
+    /// A fresh copy of this unique
+    pub fn to_item(&'static self) -> Item {
+        match self.stats {
+            UniqueStats::Weapon { kind, .. } => Item::Weapon(Weapon { unique: Some(self), ..Weapon::new(kind) }),
+            UniqueStats::Armor { kind, .. } => Item::Armor(Armor { unique: Some(self), ..Armor::new(kind) }),
+        }
+    }
+}
+
+/// A weapon instance: its type plus how worn it is
+#[derive(Clone, Debug, PartialEq)]
+pub struct Weapon {
+    pub kind: WeaponType,
+    pub durability: u32,
+    pub max_durability: u32,
+    pub unique: Option<&'static UniqueDef>,
+    pub starter: bool, // Part of a class's starting kit
+}
+
+impl Weapon {
+    pub fn new(kind: WeaponType) -> Self {
+        let max_durability = kind.max_durability();
+        Self {
+            kind,
+            durability: max_durability,
+            max_durability,
+            unique: None,
+            starter: false,
+        }
+    }
+
+    /// Starting-kit copy of a type, equippable from level 1 whatever the type asks for
+    pub fn starter(kind: WeaponType) -> Self {
+        Self { starter: true, ..Self::new(kind) }
+    }
+
+    pub fn name(&self) -> &str {
+        self.unique.map_or(self.kind.name(), |u| u.name)
+    }
+
+    /// Inclusive (min, max) damage: a unique's fixed roll, otherwise the type's
+    pub fn damage_range(&self) -> (i32, i32) {
+        match self.unique.map(|u| u.stats) {
+            Some(UniqueStats::Weapon { damage, .. }) => damage,
+            _ => self.kind.damage_range(),
+        }
+    }
+
+    pub fn average_damage(&self) -> f32 {
+        let (min, max) = self.damage_range();
+        (min + max) as f32 / 2.0
+    }
+
+    /// Damage per second before strength and dexterity: a unique's own damage at the type's swing speed
+    pub fn dps(&self) -> f32 {
+        match self.unique {
+            Some(_) => self.average_damage() / self.kind.attack_cooldown(),
+            None => self.kind.dps(),
+        }
+    }
+
+    pub fn roll_damage(&self, rng: &mut GameRng) -> i32 {
+        let (min, max) = self.damage_range();
+        rng.gen_range(min, max + 1)
+    }
+
+    /// Wear the weapon down by one use. Returns true when it breaks
+    pub fn wear(&mut self) -> bool {
+        self.durability = self.durability.saturating_sub(1);
+        self.durability == 0
+    }
+}
+
+/// An armor instance (body armor or shield): its type plus how worn it is
+#[derive(Clone, Debug, PartialEq)]
+pub struct Armor {
+    pub kind: ArmorType,
+    pub durability: u32,
+    pub max_durability: u32,
+    pub unique: Option<&'static UniqueDef>,
+    pub starter: bool, // Part of a class's starting kit
+}
+
+impl Armor {
+    pub fn new(kind: ArmorType) -> Self {
+        let max_durability = kind.max_durability();
+        Self {
+            kind,
+            durability: max_durability,
+            max_durability,
+            unique: None,
+            starter: false,
+        }
+    }
+
+    /// Starting-kit copy of a type, equippable from level 1 whatever the type asks for
+    pub fn starter(kind: ArmorType) -> Self {
+        Self { starter: true, ..Self::new(kind) }
+    }
+
+    pub fn name(&self) -> &str {
+        self.unique.map_or(self.kind.name(), |u| u.name)
+    }
+
+    /// Flat reduction against a damage type: a unique's fixed values, otherwise the type's
+    pub fn reduction_for(&self, dtype: DamageType) -> i32 {
+        match (self.unique.map(|u| u.stats), dtype) {
+            (Some(UniqueStats::Armor { physical, .. }), DamageType::Physical) => physical,
+            (Some(UniqueStats::Armor { fire, .. }), DamageType::Fire) => fire,
+            _ => self.kind.reduction_for(dtype),
+        }
+    }
+
+    /// Wear the armor down by one hit. Returns true when it breaks
+    pub fn wear(&mut self) -> bool {
+        self.durability = self.durability.saturating_sub(1);
+        self.durability == 0
+    }
 }
 
 #[derive(Clone, Debug)]
 pub enum Item {
-    Weapon(WeaponType),
-    Armor(ArmorType),
+    Weapon(Weapon),
+    Armor(Armor),
 }
 
 impl Item {
@@ -30,50 +204,224 @@ impl Item {
 
     pub fn description(&self) -> String {
         match self {
-            Item::Weapon(WeaponType::Sword) => "Damage: 1-10".to_string(),
-            Item::Weapon(WeaponType::Axe) => "Damage: 5-8".to_string(),
-            Item::Weapon(WeaponType::Mace) => "Damage: 7".to_string(),
-            Item::Armor(ArmorType::Leather) => "Reduces damage by 1".to_string(),
-            Item::Armor(ArmorType::Chainmail) => "Reduces damage by 2".to_string(),
-            Item::Armor(ArmorType::Platemail) => "Reduces damage by 4".to_string(),
+            Item::Weapon(w) if w.unique.is_some() => format!("Unique {} - {}", w.kind.name(), damage_summary(w)),
+            Item::Armor(a) if a.unique.is_some() => {
+                let reductions: Vec<String> =
+                    DamageType::ALL.iter().map(|&dtype| format!("{} -{}", dtype.name(), a.reduction_for(dtype))).collect();
+                format!("Unique {} - Reduces damage: {}", a.kind.name(), reductions.join(", "))
+            }
+            Item::Weapon(w) => match w.kind {
+                WeaponType::Sword => damage_summary(w),
+                WeaponType::Axe => format!("{}, two-handed, cleaves", damage_summary(w)),
+                WeaponType::Mace => format!("{}, may stun", damage_summary(w)),
+                WeaponType::Bow => format!("{}, ranged, two-handed", damage_summary(w)),
+                WeaponType::Staff => format!("{}, two-handed", damage_summary(w)),
+            },
+            Item::Armor(a) if a.kind.is_off_hand() => "Blocks 20% of hits".to_string(),
+            Item::Armor(a) => {
+                let reductions: Vec<String> = DamageType::ALL
+                    .iter()
+                    .map(|&dtype| format!("{} -{}", dtype.name(), a.kind.reduction_for(dtype)))
+                    .collect();
+                format!("Reduces damage: {}", reductions.join(", "))
+            }
+        }
+    }
+
+    /// Character level needed to equip; 0 means anyone can. Starting-kit gear only asks for level 1
+    pub fn required_level(&self) -> u32 {
+        match self {
+            _ if self.is_starter() => 1,
+            Item::Weapon(w) => match w.kind {
+                WeaponType::Sword => 0,
+                WeaponType::Axe => 2,
+                WeaponType::Mace => 3,
+                WeaponType::Bow => 1,
+                WeaponType::Staff => 0,
+            },
+            Item::Armor(a) => match a.kind {
+                ArmorType::Leather => 0,
+                ArmorType::Shield => 2,
+                ArmorType::Chainmail => 3,
+                ArmorType::Platemail => 5,
+            },
+        }
+    }
+
+    /// Carry weight; heavier armor and two-handers weigh the most
+    pub fn weight(&self) -> u32 {
+        match self {
+            Item::Weapon(w) => match w.kind {
+                WeaponType::Sword => 3,
+                WeaponType::Axe => 6,
+                WeaponType::Mace => 5,
+                WeaponType::Bow => 2,
+                WeaponType::Staff => 4,
+            },
+            Item::Armor(a) => match a.kind {
+                ArmorType::Leather => 4,
+                ArmorType::Shield => 5,
+                ArmorType::Chainmail => 8,
+                ArmorType::Platemail => 14,
+            },
+        }
+    }
+
+    /// Gold this item is worth in full repair; uniques are worth several times their base
+    pub fn value(&self) -> u32 {
+        let base = match self {
+            Item::Weapon(w) => match w.kind {
+                WeaponType::Sword => 40,
+                WeaponType::Axe => 50,
+                WeaponType::Mace => 60,
+                WeaponType::Bow => 45,
+                WeaponType::Staff => 35,
+            },
+            Item::Armor(a) => match a.kind {
+                ArmorType::Leather => 30,
+                ArmorType::Chainmail => 60,
+                ArmorType::Shield => 50,
+                ArmorType::Platemail => 100,
+            },
+        };
+        if self.is_unique() { base * 4 } else { base }
+    }
+
+    /// Used up from the backpack rather than equipped; no item is yet (potions are drunk at the vendor)
+    pub fn is_consumable(&self) -> bool {
+        match self {
+            Item::Weapon(_) | Item::Armor(_) => false,
+        }
+    }
+
+    /// How many share one backpack slot; gear never stacks
+    pub fn stack_size(&self) -> u32 {
+        match self {
+            Item::Weapon(_) | Item::Armor(_) => 1,
         }
     }
 
-    pub fn random() -> Item {
-        if rand::gen_range(0.0, 1.0) < 0.5 {
+    pub fn is_starter(&self) -> bool {
+        match self {
+            Item::Weapon(w) => w.starter,
+            Item::Armor(a) => a.starter,
+        }
+    }
+
+    pub fn is_unique(&self) -> bool {
+        match self {
+            Item::Weapon(w) => w.unique.is_some(),
+            Item::Armor(a) => a.unique.is_some(),
+        }
+    }
+
+    /// (current, max) durability
+    pub fn durability(&self) -> (u32, u32) {
+        match self {
+            Item::Weapon(w) => (w.durability, w.max_durability),
+            Item::Armor(a) => (a.durability, a.max_durability),
+        }
+    }
+
+    pub fn random(rng: &mut GameRng) -> Item {
+        if rng.gen_f32() < 0.5 {
             // Weapon
-            match rand::gen_range(0, 3) {
-                0 => Item::Weapon(WeaponType::Sword),
-                1 => Item::Weapon(WeaponType::Axe),
-                _ => Item::Weapon(WeaponType::Mace),
-            }
+            let kind = match rng.gen_range(0, 4) {
+                0 => WeaponType::Sword,
+                1 => WeaponType::Axe,
+                2 => WeaponType::Mace,
+                _ => WeaponType::Bow,
+            };
+            Item::Weapon(Weapon::new(kind))
         } else {
             // Armor
-            match rand::gen_range(0, 3) {
-                0 => Item::Armor(ArmorType::Leather),
-                1 => Item::Armor(ArmorType::Chainmail),
-                _ => Item::Armor(ArmorType::Platemail),
-            }
+            let kind = match rng.gen_range(0, 4) {
+                0 => ArmorType::Leather,
+                1 => ArmorType::Chainmail,
+                2 => ArmorType::Platemail,
+                _ => ArmorType::Shield,
+            };
+            Item::Armor(Armor::new(kind))
         }
     }
 }
 
 impl WeaponType {
+    pub const ALL: [WeaponType; 5] =
+        [WeaponType::Sword, WeaponType::Axe, WeaponType::Mace, WeaponType::Bow, WeaponType::Staff];
+
     pub fn name(&self) -> &str {
         match self {
             WeaponType::Sword => "Sword",
             WeaponType::Axe => "Axe",
             WeaponType::Mace => "Mace",
+            WeaponType::Bow => "Bow",
+            WeaponType::Staff => "Staff",
+        }
+    }
+
+    /// Swings before the weapon breaks
+    pub fn max_durability(&self) -> u32 {
+        match self {
+            WeaponType::Sword => 120,
+            WeaponType::Axe => 150,
+            WeaponType::Mace => 200,
+            WeaponType::Bow => 140,
+            WeaponType::Staff => 100,
         }
     }
 
-    pub fn roll_damage(&self) -> i32 {
+    /// Built-in balance numbers; `balance.cfg` can override any of them. Faster weapons hit for less.
+    pub fn default_stats(&self) -> WeaponStats {
+        let (min_damage, max_damage, attack_cooldown) = match self {
+            WeaponType::Sword => (1, 10, 0.3),
+            WeaponType::Axe => (5, 8, 0.45),
+            WeaponType::Mace => (7, 7, 0.6),
+            WeaponType::Bow => (3, 6, 0.5),
+            WeaponType::Staff => (2, 6, 0.5),
+        };
+        WeaponStats { min_damage, max_damage, attack_cooldown }
+    }
+
+    /// Inclusive (min, max) damage this weapon can roll
+    pub fn damage_range(&self) -> (i32, i32) {
+        let stats = balance::weapon(*self);
+        (stats.min_damage, stats.max_damage)
+    }
+
+    /// Seconds between swings
+    pub fn attack_cooldown(&self) -> f32 {
+        balance::weapon(*self).attack_cooldown
+    }
+
+    /// Average damage per second, so a quick Sword can be weighed against a slow Mace (there are no crits to factor in)
+    pub fn dps(&self) -> f32 {
+        let (min, max) = self.damage_range();
+        (min + max) as f32 / 2.0 / self.attack_cooldown()
+    }
+
+    /// Chance per hit to stun the target
+    pub fn stun_chance(&self) -> f32 {
         match self {
-            WeaponType::Sword => rand::gen_range(1, 11), // 1-10 inclusive
-            WeaponType::Axe => rand::gen_range(5, 9),    // 5-8 inclusive
-            WeaponType::Mace => 7,
+            WeaponType::Mace => 0.3,
+            _ => 0.0,
         }
     }
+
+    /// Cleaving weapons hit everything in the swing arc; the rest hit a single monster
+    pub fn is_cleave(&self) -> bool {
+        matches!(self, WeaponType::Axe)
+    }
+
+    /// Two-handed weapons can't be wielded alongside a shield
+    pub fn is_two_handed(&self) -> bool {
+        matches!(self, WeaponType::Axe | WeaponType::Bow | WeaponType::Staff)
+    }
+
+    /// Ranged weapons loose an arrow toward the target instead of swinging
+    pub fn is_ranged(&self) -> bool {
+        matches!(self, WeaponType::Bow)
+    }
 }
 
 impl ArmorType {
@@ -82,20 +430,71 @@ impl ArmorType {
             ArmorType::Leather => "Leather Armor",
             ArmorType::Chainmail => "Chainmail",
             ArmorType::Platemail => "Platemail",
+            ArmorType::Shield => "Shield",
         }
     }
 
-    pub fn damage_reduction(&self) -> i32 {
+    /// Hits taken (or blocked, for shields) before the armor breaks
+    pub fn max_durability(&self) -> u32 {
         match self {
-            ArmorType::Leather => 1,
-            ArmorType::Chainmail => 2,
-            ArmorType::Platemail => 4,
+            ArmorType::Leather => 60,
+            ArmorType::Chainmail => 100,
+            ArmorType::Platemail => 150,
+            ArmorType::Shield => 80,
         }
     }
+
+    /// Whether this armor goes in the off-hand slot instead of the body slot
+    pub fn is_off_hand(&self) -> bool {
+        matches!(self, ArmorType::Shield)
+    }
+
+    /// Flat reduction against a damage type. Metal turns blades but conducts heat,
+    /// so chainmail does nothing against fire while treated leather does a little.
+    pub fn reduction_for(&self, dtype: DamageType) -> i32 {
+        match (self, dtype) {
+            (ArmorType::Leather, DamageType::Physical) => 1,
+            (ArmorType::Chainmail, DamageType::Physical) => 2,
+            (ArmorType::Platemail, DamageType::Physical) => 4,
+            (ArmorType::Leather, DamageType::Fire) => 1,
+            (ArmorType::Chainmail, DamageType::Fire) => 0,
+            (ArmorType::Platemail, DamageType::Fire) => 2,
+            (ArmorType::Shield, _) => 0,
+        }
+    }
+
+    /// Chance (0.0-1.0) to fully negate an incoming hit
+    pub fn block_chance(&self) -> f32 {
+        match self {
+            ArmorType::Shield => 0.2,
+            _ => 0.0,
+        }
+    }
+
+    /// Multiplier on movement speed while worn - heavier armor is slower
+    pub fn speed_modifier(&self) -> f32 {
+        match self {
+            ArmorType::Leather | ArmorType::Shield => 1.0,
+            ArmorType::Chainmail => 0.95,
+            ArmorType::Platemail => 0.85,
+        }
+    }
+}
+
+/// `Damage: 5-8 (14.4 DPS)`, or a single number when the roll can't vary
+fn damage_summary(weapon: &Weapon) -> String {
+    let damage = match weapon.damage_range() {
+        (min, max) if min == max => min.to_string(),
+        (min, max) => format!("{}-{}", min, max),
+    };
+    format!("Damage: {} ({:.1} DPS)", damage, weapon.dps())
 }
 
-pub fn calculate_damage(base_damage: i32, armor: Option<&ArmorType>) -> i32 {
-    let reduction = armor.map(|a| a.damage_reduction()).unwrap_or(0);
-    // Minimum damage is always 1 - armor can never reduce damage to zero
-    (base_damage - reduction).max(1)
+/// Angle in radians (0..=PI) between two vectors; 0 when either has no length
+pub fn angle_between(ax: f32, ay: f32, bx: f32, by: f32) -> f32 {
+    let len = (ax * ax + ay * ay).sqrt() * (bx * bx + by * by).sqrt();
+    if len <= f32::EPSILON {
+        return 0.0;
+    }
+    ((ax * bx + ay * by) / len).clamp(-1.0, 1.0).acos()
 }
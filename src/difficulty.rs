@@ -0,0 +1,74 @@
+use macroquad::prelude::*;
+
+/// Global challenge tier, picked when a new run starts and saved with it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Difficulty {
+    Normal,
+    Hard,
+    Hell,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Normal, Difficulty::Hard, Difficulty::Hell];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Hell => "Hell",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        Self::ALL.into_iter().find(|d| d.name() == name)
+    }
+
+    /// Next tier in menu order, wrapping around (`step` is +1 or -1)
+    pub fn cycle(self, step: i32) -> Difficulty {
+        let index = Self::ALL.iter().position(|&d| d == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+
+    pub fn health_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+            Difficulty::Hell => 2.5,
+        }
+    }
+
+    pub fn damage_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.4,
+            Difficulty::Hell => 2.0,
+        }
+    }
+
+    /// Scales drop chance and gold so harder tiers pay out more
+    pub fn loot_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.3,
+            Difficulty::Hell => 1.6,
+        }
+    }
+
+    /// Dying ends the run for good instead of respawning
+    pub fn is_permadeath(&self) -> bool {
+        matches!(self, Difficulty::Hell)
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Difficulty::Normal => LIGHTGRAY,
+            Difficulty::Hard => ORANGE,
+            Difficulty::Hell => RED,
+        }
+    }
+}
+
+/// Apply a multiplier to an integer stat; a 1.0 multiplier returns `value` unchanged
+pub fn scale(value: i32, multiplier: f32) -> i32 {
+    (value as f32 * multiplier).round() as i32
+}
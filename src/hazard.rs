@@ -0,0 +1,103 @@
+use macroquad::prelude::*;
+
+use crate::camera::{GameCamera, TILE_HEIGHT, TILE_WIDTH};
+
+/// Drifting desert sandstorm that hurts the player and monsters caught inside
+pub struct Hazard {
+    pub x: f32,
+    pub y: f32,
+    vx: f32, // Tiles per second
+    vy: f32,
+    pub radius: f32,
+    pub dps: f32,
+    age: f32,
+    tick_timer: f32, // Damage is dealt in pulses rather than every frame
+}
+
+impl Hazard {
+    pub const WARMUP: f32 = 2.0; // Harmless while it gathers, so the player sees it coming
+    const LIFETIME: f32 = 14.0;
+    const FADE_TIME: f32 = 1.5;
+    const DRIFT_SPEED: f32 = 0.6;
+    pub const TICK_INTERVAL: f32 = 0.5;
+
+    pub fn new_sandstorm(x: f32, y: f32) -> Self {
+        let angle = rand::gen_range(0.0, std::f32::consts::TAU);
+        Self {
+            x,
+            y,
+            vx: angle.cos() * Self::DRIFT_SPEED,
+            vy: angle.sin() * Self::DRIFT_SPEED,
+            radius: rand::gen_range(2.0, 3.2),
+            dps: 4.0,
+            age: 0.0,
+            tick_timer: Self::TICK_INTERVAL,
+        }
+    }
+
+    /// Drift and age; returns false once the storm has blown out
+    pub fn update(&mut self, dt: f32) -> bool {
+        self.x += self.vx * dt;
+        self.y += self.vy * dt;
+        self.age += dt;
+        self.age < Self::LIFETIME
+    }
+
+    /// Whether a damage pulse lands this frame (never during the warm-up)
+    pub fn tick(&mut self, dt: f32) -> bool {
+        if self.age < Self::WARMUP {
+            return false;
+        }
+        self.tick_timer -= dt;
+        if self.tick_timer <= 0.0 {
+            self.tick_timer += Self::TICK_INTERVAL;
+            return true;
+        }
+        false
+    }
+
+    /// Damage per pulse, at least 1
+    pub fn pulse_damage(&self) -> i32 {
+        ((self.dps * Self::TICK_INTERVAL).round() as i32).max(1)
+    }
+
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let dx = x - self.x;
+        let dy = y - self.y;
+        dx * dx + dy * dy <= self.radius * self.radius
+    }
+
+    pub fn draw(&self, camera: &GameCamera) {
+        let (screen_x, screen_y) = camera.world_to_screen(self.x, self.y);
+        // A circle of `radius` tiles is an ellipse on the isometric ground
+        let rx = self.radius * TILE_WIDTH / 2.0 * std::f32::consts::SQRT_2;
+        let ry = self.radius * TILE_HEIGHT / 2.0 * std::f32::consts::SQRT_2;
+
+        let fade = ((Self::LIFETIME - self.age) / Self::FADE_TIME).clamp(0.0, 1.0);
+        let forming = self.age < Self::WARMUP;
+        let strength = if forming { self.age / Self::WARMUP } else { 1.0 } * fade;
+
+        draw_ellipse(screen_x, screen_y, rx, ry, 0.0, Color::new(0.85, 0.7, 0.4, 0.3 * strength));
+
+        // Swirling dust bands
+        let spin = self.age * 2.5;
+        for band in 0..3 {
+            let scale = 0.35 + band as f32 * 0.25;
+            for i in 0..8 {
+                let angle = spin * (1.0 + band as f32 * 0.3) + i as f32 * std::f32::consts::TAU / 8.0;
+                let px = screen_x + angle.cos() * rx * scale;
+                let py = screen_y + angle.sin() * ry * scale - 6.0;
+                draw_circle(px, py, 3.0 + band as f32, Color::new(0.75, 0.6, 0.35, 0.45 * strength));
+            }
+        }
+
+        // Warning rim: pulses while forming, solid once it starts hurting
+        let rim_alpha = if forming { 0.4 + 0.4 * (self.age * 10.0).sin().abs() } else { 0.6 };
+        draw_ellipse_lines(screen_x, screen_y, rx, ry, 0.0, 2.0, Color::new(1.0, 0.55, 0.1, rim_alpha * fade));
+        if forming {
+            let label = "Sandstorm!";
+            let dims = measure_text(label, None, 18, 1.0);
+            draw_text(label, screen_x - dims.width / 2.0, screen_y - ry - 8.0, 18.0, ORANGE);
+        }
+    }
+}
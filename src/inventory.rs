@@ -1,9 +1,136 @@
 use macroquad::prelude::*;
+use std::cmp::Reverse;
 
-use crate::combat::Item;
+use crate::combat::{DamageType, Item, Weapon};
 use crate::player::Player;
+use crate::ui::Palette;
+use crate::vendor;
+
+pub const INVENTORY_SIZE: usize = 24;
+pub const SLOTS_PER_PAGE: usize = 8;
+pub const PAGE_COUNT: usize = INVENTORY_SIZE.div_ceil(SLOTS_PER_PAGE);
+
+const PANEL_W: f32 = 400.0;
+const PANEL_H: f32 = 500.0;
+const SLOT_SIZE: f32 = 50.0;
+const SLOT_PADDING: f32 = 10.0;
+const SORT_BUTTON_W: f32 = 70.0;
+const SORT_BUTTON_H: f32 = 28.0;
+pub const SLOTS_PER_ROW: usize = 4;
+const UNIQUE_COLOR: Color = Color::new(0.9, 0.72, 0.27, 1.0);
+
+/// An equipment row on the inventory screen, top to bottom
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EquipSlot {
+    Weapon,
+    Armor,
+    OffHand,
+}
+
+impl EquipSlot {
+    const ALL: [EquipSlot; 3] = [EquipSlot::Weapon, EquipSlot::Armor, EquipSlot::OffHand];
+}
+
+/// An entry in the backpack's right-click menu, top to bottom
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlotAction {
+    Equip,
+    Drop,
+    Use,
+    Split,
+}
+
+impl SlotAction {
+    const ALL: [SlotAction; 4] = [SlotAction::Equip, SlotAction::Drop, SlotAction::Use, SlotAction::Split];
+
+    fn label(&self) -> &'static str {
+        match self {
+            SlotAction::Equip => "Equip",
+            SlotAction::Drop => "Drop",
+            SlotAction::Use => "Use",
+            SlotAction::Split => "Split stack",
+        }
+    }
+
+    /// Whether the action applies to `item`; greyed out otherwise
+    pub fn is_enabled(&self, item: &Item) -> bool {
+        match self {
+            SlotAction::Equip | SlotAction::Drop => true,
+            SlotAction::Use => item.is_consumable(),
+            SlotAction::Split => item.stack_size() > 1,
+        }
+    }
+}
+
+/// Right-click menu open on a backpack slot, anchored where it was opened
+#[derive(Clone, Copy, Debug)]
+pub struct ContextMenu {
+    pub slot: usize, // Absolute backpack index
+    x: f32,
+    y: f32,
+}
+
+impl ContextMenu {
+    const ROW_W: f32 = 120.0;
+    const ROW_H: f32 = 22.0;
 
-pub const INVENTORY_SIZE: usize = 8;
+    /// A menu for the filled slot right-clicked this frame, if any, kept fully on screen
+    pub fn open_clicked(player: &Player, page: usize, scale: f32) -> Option<ContextMenu> {
+        if !is_mouse_button_pressed(MouseButton::Right) {
+            return None;
+        }
+        let slot = slot_under_mouse(page, scale).filter(|&slot| slot < player.inventory.count())?;
+        let (mouse_x, mouse_y) = mouse_position();
+        let height = Self::ROW_H * scale * SlotAction::ALL.len() as f32;
+        Some(ContextMenu {
+            slot,
+            x: mouse_x.min(screen_width() - Self::ROW_W * scale).max(0.0),
+            y: mouse_y.min(screen_height() - height).max(0.0),
+        })
+    }
+
+    fn row_rect(&self, row: usize, scale: f32) -> Rect {
+        let row_h = Self::ROW_H * scale;
+        Rect::new(self.x, self.y + row as f32 * row_h, Self::ROW_W * scale, row_h)
+    }
+
+    fn action_under_mouse(&self, scale: f32) -> Option<SlotAction> {
+        (0..SlotAction::ALL.len())
+            .find(|&row| self.row_rect(row, scale).contains(mouse_position().into()))
+            .map(|row| SlotAction::ALL[row])
+    }
+
+    /// Whether the mouse is over any of the menu's rows
+    pub fn contains_mouse(&self, scale: f32) -> bool {
+        self.action_under_mouse(scale).is_some()
+    }
+
+    /// Action clicked this frame, if any (greyed-out rows included; the caller checks them)
+    pub fn clicked_action(&self, scale: f32) -> Option<SlotAction> {
+        if !is_mouse_button_pressed(MouseButton::Left) {
+            return None;
+        }
+        self.action_under_mouse(scale)
+    }
+
+    fn draw(&self, item: &Item, scale: f32) {
+        let hovered = self.action_under_mouse(scale);
+        for (row, action) in SlotAction::ALL.into_iter().enumerate() {
+            let rect = self.row_rect(row, scale);
+            let enabled = action.is_enabled(item);
+            let fill = if enabled && hovered == Some(action) {
+                Color::from_rgba(80, 80, 100, 255)
+            } else {
+                Color::from_rgba(20, 20, 30, 240)
+            };
+            draw_rectangle(rect.x, rect.y, rect.w, rect.h, fill);
+            let color = if enabled { WHITE } else { DARKGRAY };
+            draw_text(action.label(), rect.x + 8.0 * scale, rect.y + 16.0 * scale, 16.0 * scale, color);
+        }
+        let height = Self::ROW_H * scale * SlotAction::ALL.len() as f32;
+        draw_rectangle_lines(self.x, self.y, Self::ROW_W * scale, height, 1.0, GRAY);
+    }
+}
 
 #[derive(Clone)]
 pub struct Inventory {
@@ -32,6 +159,33 @@ impl Inventory {
         }
     }
 
+    /// Move the item at `from` onto slot `to`: swaps with an occupied slot, or moves to the
+    /// end of the backpack when `to` is empty (items are always packed from slot 0)
+    pub fn move_item(&mut self, from: usize, to: usize) {
+        if from >= self.items.len() || from == to {
+            return;
+        }
+        if to < self.items.len() {
+            self.items.swap(from, to);
+        } else {
+            let item = self.items.remove(from);
+            self.items.push(item);
+        }
+    }
+
+    /// Weapons first, then armor, most valuable first within each group. The sort is stable,
+    /// so identical items keep their relative order. Only the backpack moves - equipped gear
+    /// isn't stored here.
+    pub fn sort(&mut self) {
+        self.items.sort_by_key(|item| {
+            let group = match item {
+                Item::Weapon(_) => 0,
+                Item::Armor(_) => 1,
+            };
+            (group, Reverse(vendor::buy_price(item)))
+        });
+    }
+
     pub fn is_full(&self) -> bool {
         self.items.len() >= INVENTORY_SIZE
     }
@@ -45,219 +199,526 @@ pub struct GroundItem {
     pub x: f32,
     pub y: f32,
     pub item: Item,
+    pub lifetime: f32, // Seconds until it despawns
+    pub thrown: bool,  // The player's own thrown weapon: never despawns, re-equipped on pickup
+    pub dropped: bool, // Put down by the player: auto-pickup and the magnet skip it until they step away
+}
+
+impl GroundItem {
+    pub const LIFETIME: f32 = 60.0;
+    const UNIQUE_LIFETIME: f32 = 300.0; // Rare finds wait much longer for the player to come back
+    pub const BLINK_TIME: f32 = 5.0; // Blinks for this long before vanishing
+    const KEEP_RANGE: f32 = 2.0; // Never expires while the player is this close
+
+    pub fn new(x: f32, y: f32, item: Item) -> Self {
+        let lifetime = if item.is_unique() { Self::UNIQUE_LIFETIME } else { Self::LIFETIME };
+        Self { x, y, item, lifetime, thrown: false, dropped: false }
+    }
+
+    /// Dropped from the backpack at the player's feet
+    pub fn dropped(x: f32, y: f32, item: Item) -> Self {
+        Self { dropped: true, ..Self::new(x, y, item) }
+    }
+
+    /// Hand a dropped item back to auto-pickup once the player is beyond `reach` of it
+    pub fn release_if_clear(&mut self, player_x: f32, player_y: f32, reach: f32) {
+        let (dx, dy) = (self.x - player_x, self.y - player_y);
+        if self.dropped && dx * dx + dy * dy > reach * reach {
+            self.dropped = false;
+        }
+    }
+
+    /// Where a thrown weapon came down
+    pub fn thrown(x: f32, y: f32, weapon: Weapon) -> Self {
+        Self { thrown: true, ..Self::new(x, y, Item::Weapon(weapon)) }
+    }
+
+    const MAGNET_RATE: f32 = 6.0; // Exponential rate the gap to the player closes at
+
+    /// Drift toward the player. Each frame covers a fraction of the remaining gap, so it eases
+    /// in and can never pass the player.
+    pub fn pull_toward(&mut self, player_x: f32, player_y: f32, dt: f32) {
+        let t = 1.0 - (-Self::MAGNET_RATE * dt).exp();
+        self.x += (player_x - self.x) * t;
+        self.y += (player_y - self.y) * t;
+    }
+
+    /// Tick the despawn timer; returns false once the item should be removed
+    pub fn update(&mut self, dt: f32, player_x: f32, player_y: f32) -> bool {
+        let dx = self.x - player_x;
+        let dy = self.y - player_y;
+        if !self.thrown && dx * dx + dy * dy > Self::KEEP_RANGE * Self::KEEP_RANGE {
+            self.lifetime -= dt;
+        }
+        self.lifetime > 0.0
+    }
 }
 
-pub fn draw_ground_item(ground_item: &GroundItem, screen_x: f32, screen_y: f32) {
-    let color = match &ground_item.item {
+/// Name color on the ground and in tooltips: uniques stand out from both categories
+fn item_color(item: &Item) -> Color {
+    match item {
+        _ if item.is_unique() => UNIQUE_COLOR,
         Item::Weapon(_) => ORANGE,
         Item::Armor(_) => SKYBLUE,
-    };
+    }
+}
+
+/// Crossed lines inside a unique's diamond so it reads without telling gold from orange
+fn draw_unique_mark(x: f32, y: f32, size: f32) {
+    let half = size * 0.6;
+    draw_line(x - half, y, x + half, y, 1.5, BLACK);
+    draw_line(x, y - half, x, y + half, 1.5, BLACK);
+    draw_poly_lines(x, y, 4, size * 0.45, 45.0, 1.0, BLACK);
+}
+
+/// `highlighted` outlines items the player can pick up manually from where they stand
+pub fn draw_ground_item(ground_item: &GroundItem, screen_x: f32, screen_y: f32, highlighted: bool, palette: Palette) {
+    // Blink faster and faster as the item is about to despawn
+    if ground_item.lifetime < GroundItem::BLINK_TIME {
+        let rate = 4.0 + (GroundItem::BLINK_TIME - ground_item.lifetime) * 2.0;
+        if (ground_item.lifetime * rate).fract() < 0.35 {
+            return;
+        }
+    }
+
+    let color = item_color(&ground_item.item);
 
     // Draw as a small diamond
     let size = 8.0;
     draw_poly(screen_x, screen_y, 4, size, 45.0, color);
     draw_poly_lines(screen_x, screen_y, 4, size, 45.0, 1.5, WHITE);
+    if palette.marks_uniques() && ground_item.item.is_unique() {
+        draw_unique_mark(screen_x, screen_y, size);
+    }
+    if highlighted {
+        draw_poly_lines(screen_x, screen_y, 4, size + 4.0, 45.0, 2.0, GOLD);
+    }
 }
 
-// Returns the index of clicked inventory slot, if any
-pub fn get_clicked_slot() -> Option<usize> {
-    if !is_mouse_button_pressed(MouseButton::Left) {
-        return None;
+/// What the player dropped on death; walking over it recovers the loot
+pub struct Corpse {
+    pub x: f32,
+    pub y: f32,
+    pub gold: u32,
+    pub items: Vec<Item>,
+}
+
+impl Corpse {
+    pub fn is_empty(&self) -> bool {
+        self.gold == 0 && self.items.is_empty()
     }
 
+    pub fn draw(&self, screen_x: f32, screen_y: f32) {
+        // Bones on a dark stain, with a gravestone-like marker so it's easy to spot
+        draw_ellipse(screen_x, screen_y, 18.0, 9.0, 0.0, Color::from_rgba(60, 10, 10, 180));
+        let bone = Color::from_rgba(230, 225, 205, 255);
+        draw_line(screen_x - 10.0, screen_y - 3.0, screen_x + 10.0, screen_y + 3.0, 3.0, bone);
+        draw_line(screen_x - 10.0, screen_y + 3.0, screen_x + 10.0, screen_y - 3.0, 3.0, bone);
+        draw_circle(screen_x, screen_y - 8.0, 5.0, bone);
+
+        let label = "Your corpse";
+        let dims = measure_text(label, None, 14, 1.0);
+        draw_text(label, screen_x - dims.width / 2.0, screen_y - 20.0, 14.0, Color::from_rgba(255, 120, 120, 255));
+    }
+}
+
+/// Top-left corner of the inventory panel, centered on the current screen size.
+/// Every layout helper takes the UI scale, so drawing and hit-testing grow together.
+fn panel_origin(scale: f32) -> (f32, f32) {
+    // Pinned to the top-left if the window shrinks below the panel size
+    (
+        (screen_width() / 2.0 - PANEL_W * scale / 2.0).max(0.0),
+        (screen_height() / 2.0 - PANEL_H * scale / 2.0).max(0.0),
+    )
+}
+
+/// Top-left corner of the slot at `page_index` (0..SLOTS_PER_PAGE) on the visible page.
+/// Drawing and hit-testing both go through here so they can't drift apart.
+fn slot_position(page_index: usize, scale: f32) -> (f32, f32) {
+    let (panel_x, panel_y) = panel_origin(scale);
+    let start_x = panel_x + 20.0 * scale;
+    let start_y = panel_y + 225.0 * scale;
+
+    let row = page_index / SLOTS_PER_ROW;
+    let col = page_index % SLOTS_PER_ROW;
+    (
+        start_x + col as f32 * (SLOT_SIZE + SLOT_PADDING) * scale,
+        start_y + row as f32 * (SLOT_SIZE + SLOT_PADDING) * scale,
+    )
+}
+
+/// Absolute inventory index of the slot under the mouse on `page`, if any
+fn slot_under_mouse(page: usize, scale: f32) -> Option<usize> {
     let (mouse_x, mouse_y) = mouse_position();
-    let screen_w = screen_width();
-    let screen_h = screen_height();
+    let size = SLOT_SIZE * scale;
 
-    let panel_w = 400.0;
-    let panel_h = 500.0;
-    let panel_x = screen_w / 2.0 - panel_w / 2.0;
-    let panel_y = screen_h / 2.0 - panel_h / 2.0;
-
-    let slot_size = 50.0;
-    let slot_padding = 10.0;
-    let slots_per_row = 4;
-    let start_x = panel_x + 20.0;
-    let start_y = panel_y + 200.0;
-
-    for i in 0..INVENTORY_SIZE {
-        let row = i / slots_per_row;
-        let col = i % slots_per_row;
-        let slot_x = start_x + col as f32 * (slot_size + slot_padding);
-        let slot_y = start_y + row as f32 * (slot_size + slot_padding);
-
-        if mouse_x >= slot_x
-            && mouse_x <= slot_x + slot_size
+    (0..SLOTS_PER_PAGE).find_map(|i| {
+        let (slot_x, slot_y) = slot_position(i, scale);
+        let inside = mouse_x >= slot_x
+            && mouse_x <= slot_x + size
             && mouse_y >= slot_y
-            && mouse_y <= slot_y + slot_size
-        {
-            return Some(i);
-        }
+            && mouse_y <= slot_y + size;
+        let index = page * SLOTS_PER_PAGE + i;
+        (inside && index < INVENTORY_SIZE).then_some(index)
+    })
+}
+
+// Returns the absolute index of clicked inventory slot, if any
+pub fn get_clicked_slot(page: usize, scale: f32) -> Option<usize> {
+    if !is_mouse_button_pressed(MouseButton::Left) {
+        return None;
     }
+    slot_under_mouse(page, scale)
+}
 
-    None
+fn sort_button_rect(scale: f32) -> Rect {
+    let (panel_x, panel_y) = panel_origin(scale);
+    Rect::new(
+        panel_x + (PANEL_W - SORT_BUTTON_W - 20.0) * scale,
+        panel_y + 14.0 * scale,
+        SORT_BUTTON_W * scale,
+        SORT_BUTTON_H * scale,
+    )
 }
 
-// Returns the index of hovered inventory slot, if any
-fn get_hovered_slot() -> Option<usize> {
-    let (mouse_x, mouse_y) = mouse_position();
-    let screen_w = screen_width();
-    let screen_h = screen_height();
+pub fn sort_button_clicked(scale: f32) -> bool {
+    is_mouse_button_pressed(MouseButton::Left) && sort_button_rect(scale).contains(mouse_position().into())
+}
 
-    let panel_w = 400.0;
-    let panel_h = 500.0;
-    let panel_x = screen_w / 2.0 - panel_w / 2.0;
-    let panel_y = screen_h / 2.0 - panel_h / 2.0;
-
-    let slot_size = 50.0;
-    let slot_padding = 10.0;
-    let slots_per_row = 4;
-    let start_x = panel_x + 20.0;
-    let start_y = panel_y + 200.0;
-
-    for i in 0..INVENTORY_SIZE {
-        let row = i / slots_per_row;
-        let col = i % slots_per_row;
-        let slot_x = start_x + col as f32 * (slot_size + slot_padding);
-        let slot_y = start_y + row as f32 * (slot_size + slot_padding);
-
-        if mouse_x >= slot_x
-            && mouse_x <= slot_x + slot_size
-            && mouse_y >= slot_y
-            && mouse_y <= slot_y + slot_size
-        {
-            return Some(i);
-        }
+/// Clickable area of an equipment row; its text baseline sits 16px down
+fn equip_row_rect(slot: EquipSlot, scale: f32) -> Rect {
+    let (panel_x, panel_y) = panel_origin(scale);
+    let row = EquipSlot::ALL.iter().position(|&s| s == slot).unwrap_or(0);
+    Rect::new(panel_x + 24.0 * scale, panel_y + (94.0 + row as f32 * 25.0) * scale, 216.0 * scale, 22.0 * scale)
+}
+
+fn equip_row_under_mouse(scale: f32) -> Option<EquipSlot> {
+    EquipSlot::ALL.into_iter().find(|&slot| equip_row_rect(slot, scale).contains(mouse_position().into()))
+}
+
+/// Equipment row clicked to unequip it, if any
+pub fn get_clicked_equip_slot(scale: f32) -> Option<EquipSlot> {
+    if !is_mouse_button_pressed(MouseButton::Left) {
+        return None;
     }
+    equip_row_under_mouse(scale)
+}
+
+// Returns the absolute index of hovered inventory slot, if any
+pub fn get_hovered_slot(page: usize, scale: f32) -> Option<usize> {
+    slot_under_mouse(page, scale)
+}
 
-    None
+/// Font size `size` at the UI scale, for `measure_text`
+fn font(size: f32, scale: f32) -> u16 {
+    (size * scale).round() as u16
 }
 
-pub fn draw_inventory_screen(player: &Player) {
+/// Draw the inventory; `dragging` is the absolute index of an item being dragged, if any,
+/// `selected` the keyboard cursor and `menu` an open right-click menu
+pub fn draw_inventory_screen(
+    player: &Player,
+    page: usize,
+    dragging: Option<usize>,
+    selected: usize,
+    menu: Option<&ContextMenu>,
+    palette: Palette,
+    scale: f32,
+) {
     let screen_w = screen_width();
     let screen_h = screen_height();
+    let s = scale;
 
     // Darken background
     draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::from_rgba(0, 0, 0, 180));
 
     // Inventory panel
-    let panel_w = 400.0;
-    let panel_h = 500.0;
-    let panel_x = screen_w / 2.0 - panel_w / 2.0;
-    let panel_y = screen_h / 2.0 - panel_h / 2.0;
+    let panel_w = PANEL_W * s;
+    let panel_h = PANEL_H * s;
+    let (panel_x, panel_y) = panel_origin(s);
 
     draw_rectangle(panel_x, panel_y, panel_w, panel_h, Color::from_rgba(40, 40, 50, 255));
     draw_rectangle_lines(panel_x, panel_y, panel_w, panel_h, 2.0, WHITE);
 
     // Title
-    draw_text("INVENTORY", panel_x + 20.0, panel_y + 35.0, 32.0, WHITE);
+    draw_text("INVENTORY", panel_x + 20.0 * s, panel_y + 35.0 * s, 32.0 * s, WHITE);
+
+    // Sort button
+    let sort = sort_button_rect(s);
+    let sort_hovered = sort.contains(mouse_position().into());
+    let sort_fill = if sort_hovered { Color::from_rgba(80, 80, 100, 255) } else { Color::from_rgba(60, 60, 70, 255) };
+    draw_rectangle(sort.x, sort.y, sort.w, sort.h, sort_fill);
+    draw_rectangle_lines(sort.x, sort.y, sort.w, sort.h, 1.0, if sort_hovered { WHITE } else { GRAY });
+    let sort_dims = measure_text("Sort", None, font(18.0, s), 1.0);
+    draw_text("Sort", sort.x + (sort.w - sort_dims.width) / 2.0, sort.y + 19.0 * s, 18.0 * s, WHITE);
 
     // Equipment section
-    draw_text("Equipped:", panel_x + 20.0, panel_y + 80.0, 20.0, GRAY);
+    draw_text("Equipped:", panel_x + 20.0 * s, panel_y + 80.0 * s, 20.0 * s, GRAY);
+
+    // Filled rows light up under the mouse; clicking one unequips it
+    let hovered_equip = equip_row_under_mouse(s).filter(|&slot| match slot {
+        EquipSlot::Weapon => player.weapon.is_some(),
+        EquipSlot::Armor => player.armor.is_some(),
+        EquipSlot::OffHand => player.off_hand.is_some(),
+    });
+    if let Some(slot) = hovered_equip {
+        let row = equip_row_rect(slot, s);
+        draw_rectangle(row.x, row.y, row.w, row.h, Color::from_rgba(80, 80, 100, 255));
+    }
 
     // Weapon slot
-    let weapon_name = player.weapon.name();
+    let weapon_name = player.weapon.as_ref().map(|w| w.name()).unwrap_or("Fists");
+    let (min_damage, max_damage) = player.damage_range();
     draw_text(
-        &format!("Weapon: {}", weapon_name),
-        panel_x + 30.0,
-        panel_y + 110.0,
-        18.0,
+        &format!("Weapon: {} ({}-{})", weapon_name, min_damage, max_damage),
+        panel_x + 30.0 * s,
+        panel_y + 110.0 * s,
+        18.0 * s,
         ORANGE,
     );
 
     // Armor slot
     let armor_name = player.armor.as_ref().map(|a| a.name()).unwrap_or("None");
+    let armor_reduction = player.damage_reduction();
     draw_text(
-        &format!("Armor: {}", armor_name),
-        panel_x + 30.0,
-        panel_y + 135.0,
-        18.0,
+        &format!("Armor: {} ({})", armor_name, armor_reduction),
+        panel_x + 30.0 * s,
+        panel_y + 135.0 * s,
+        18.0 * s,
         SKYBLUE,
     );
 
-    // Inventory grid
-    draw_text("Backpack (click to equip):", panel_x + 20.0, panel_y + 180.0, 20.0, GRAY);
+    // Off-hand slot
+    let two_handed = player.weapon.as_ref().is_some_and(|w| w.kind.is_two_handed());
+    let off_hand_name = match &player.off_hand {
+        Some(armor) => armor.name(),
+        None if two_handed => "- (two-handed weapon)",
+        None => "None",
+    };
+    draw_text(
+        &format!("Off-hand: {}", off_hand_name),
+        panel_x + 30.0 * s,
+        panel_y + 160.0 * s,
+        18.0 * s,
+        SKYBLUE,
+    );
 
-    let slot_size = 50.0;
-    let slot_padding = 10.0;
-    let slots_per_row = 4;
-    let start_x = panel_x + 20.0;
-    let start_y = panel_y + 200.0;
+    // Effective stats (read straight from the player, so they update as soon as an item is equipped)
+    let stats_x = panel_x + 250.0 * s;
+    draw_text("Stats:", stats_x, panel_y + 80.0 * s, 20.0 * s, GRAY);
+    let stats = [
+        format!("Health: {}/{}", player.health, player.max_health()),
+        format!("Damage: {}-{}", min_damage, max_damage),
+        format!(
+            "Reduction: {} / {} fire",
+            armor_reduction,
+            player.damage_reduction_for(DamageType::Fire)
+        ),
+        format!("Block: {:.0}%", player.block_chance() * 100.0),
+        format!("Speed: {:.0}%", player.move_speed() / Player::BASE_SPEED * 100.0),
+    ];
+    for (i, line) in stats.iter().enumerate() {
+        draw_text(line, stats_x + 10.0 * s, panel_y + (110.0 + i as f32 * 25.0) * s, 18.0 * s, LIGHTGRAY);
+    }
+    let (weight_text, weight_color) = if player.is_encumbered() {
+        (format!("Weight: {}/{} (encumbered)", player.carried_weight(), player.carry_capacity()), palette.bad())
+    } else {
+        (format!("Weight: {}/{}", player.carried_weight(), player.carry_capacity()), LIGHTGRAY)
+    };
+    let weight_y = panel_y + (110.0 + stats.len() as f32 * 25.0) * s;
+    draw_text(&weight_text, stats_x + 10.0 * s, weight_y, 18.0 * s, weight_color);
 
-    let hovered_slot = get_hovered_slot();
+    // Inventory grid
+    draw_text("Backpack:", panel_x + 20.0 * s, panel_y + 205.0 * s, 20.0 * s, GRAY);
 
-    for i in 0..INVENTORY_SIZE {
-        let row = i / slots_per_row;
-        let col = i % slots_per_row;
-        let slot_x = start_x + col as f32 * (slot_size + slot_padding);
-        let slot_y = start_y + row as f32 * (slot_size + slot_padding);
+    let hovered_slot = get_hovered_slot(page, s);
+    let slot_size = SLOT_SIZE * s;
+
+    for i in 0..SLOTS_PER_PAGE {
+        let index = page * SLOTS_PER_PAGE + i;
+        if index >= INVENTORY_SIZE {
+            break;
+        }
+        let (slot_x, slot_y) = slot_position(i, s);
 
         // Draw slot background (highlight if hovered)
-        let bg_color = if hovered_slot == Some(i) && player.inventory.items.get(i).is_some() {
+        let bg_color = if hovered_slot == Some(index) && player.inventory.items.get(index).is_some() {
             Color::from_rgba(80, 80, 100, 255)
         } else {
             Color::from_rgba(60, 60, 70, 255)
         };
         draw_rectangle(slot_x, slot_y, slot_size, slot_size, bg_color);
-        draw_rectangle_lines(slot_x, slot_y, slot_size, slot_size, 1.0, GRAY);
+        if index == selected {
+            draw_rectangle_lines(slot_x - 1.0, slot_y - 1.0, slot_size + 2.0, slot_size + 2.0, 2.0, GOLD);
+        } else {
+            draw_rectangle_lines(slot_x, slot_y, slot_size, slot_size, 1.0, GRAY);
+        }
 
-        // Draw item if present
-        if let Some(item) = player.inventory.items.get(i) {
-            let color = match item {
-                Item::Weapon(_) => ORANGE,
-                Item::Armor(_) => SKYBLUE,
-            };
-            draw_poly(
-                slot_x + slot_size / 2.0,
-                slot_y + slot_size / 2.0,
-                4,
-                15.0,
-                45.0,
-                color,
-            );
+        // Draw item if present (a dragged item follows the cursor instead)
+        if let Some(item) = player.inventory.items.get(index) {
+            if dragging == Some(index) {
+                let inset = 3.0 * s;
+                draw_rectangle_lines(
+                    slot_x + inset,
+                    slot_y + inset,
+                    slot_size - inset * 2.0,
+                    slot_size - inset * 2.0,
+                    1.0,
+                    DARKGRAY,
+                );
+            } else {
+                draw_slot_item(item, slot_x, slot_y, palette, s);
+            }
         }
     }
 
-    // Draw tooltip for hovered item
-    if let Some(slot_idx) = hovered_slot {
-        if let Some(item) = player.inventory.items.get(slot_idx) {
-            let (mouse_x, mouse_y) = mouse_position();
-            draw_tooltip(mouse_x + 15.0, mouse_y + 15.0, item);
-        }
+    // Page indicator under the grid
+    let (_, last_row_y) = slot_position(SLOTS_PER_PAGE - 1, s);
+    draw_text(
+        &format!("Page {}/{}  (PgUp/PgDn or scroll)", page + 1, PAGE_COUNT),
+        panel_x + 20.0 * s,
+        last_row_y + slot_size + 25.0 * s,
+        16.0 * s,
+        LIGHTGRAY,
+    );
+
+    // Tooltip for the hovered item, or beside the keyboard cursor when the mouse is off the grid
+    // (not while dragging - the held item is what matters - or while a menu is open)
+    let tooltip_slot = hovered_slot.unwrap_or(selected);
+    if dragging.is_none()
+        && menu.is_none()
+        && let Some(item) = player.inventory.items.get(tooltip_slot)
+    {
+        let (tip_x, tip_y) = match hovered_slot {
+            Some(_) => {
+                let (mouse_x, mouse_y) = mouse_position();
+                (mouse_x + 15.0, mouse_y + 15.0)
+            }
+            None => {
+                let (slot_x, slot_y) = slot_position(selected % SLOTS_PER_PAGE, s);
+                (slot_x + slot_size + 10.0 * s, slot_y)
+            }
+        };
+        draw_tooltip(tip_x, tip_y, item, player, None, palette, s);
     }
 
     // Item count
     draw_text(
         &format!("{}/{} slots used", player.inventory.count(), INVENTORY_SIZE),
-        panel_x + 20.0,
-        panel_y + panel_h - 40.0,
-        16.0,
+        panel_x + 20.0 * s,
+        panel_y + panel_h - 40.0 * s,
+        16.0 * s,
         GRAY,
     );
 
     // Instructions
     draw_text(
-        "Click item to equip | Press I or ESC to close",
-        panel_x + 20.0,
-        panel_y + panel_h - 20.0,
-        14.0,
+        "Drag to rearrange | Double-click or ENTER to equip | X to drop | I or ESC to close",
+        panel_x + 20.0 * s,
+        panel_y + panel_h - 20.0 * s,
+        14.0 * s,
         GRAY,
     );
+
+    if hovered_equip.is_some() && dragging.is_none() && menu.is_none() {
+        let (mouse_x, mouse_y) = mouse_position();
+        let label = if player.inventory.is_full() { "Backpack full" } else { "Click to unequip" };
+        let dims = measure_text(label, None, font(14.0, s), 1.0);
+        let (box_x, box_y) = (mouse_x + 12.0, mouse_y + 12.0);
+        draw_rectangle(box_x, box_y, dims.width + 12.0 * s, 20.0 * s, Color::from_rgba(20, 20, 25, 230));
+        draw_text(label, box_x + 6.0 * s, box_y + 14.0 * s, 14.0 * s, LIGHTGRAY);
+    }
+
+    if let Some(menu) = menu
+        && let Some(item) = player.inventory.items.get(menu.slot)
+    {
+        menu.draw(item, s);
+    }
+
+    // Held item drawn last so it sits above the panel
+    if let Some(item) = dragging.and_then(|index| player.inventory.items.get(index)) {
+        let (mouse_x, mouse_y) = mouse_position();
+        draw_slot_item(item, mouse_x - slot_size / 2.0, mouse_y - slot_size / 2.0, palette, s);
+    }
+}
+
+/// Item icon and durability bar inside a slot whose top-left corner is (x, y)
+fn draw_slot_item(item: &Item, x: f32, y: f32, palette: Palette, scale: f32) {
+    let size = SLOT_SIZE * scale;
+    let (center_x, center_y) = (x + size / 2.0, y + size / 2.0);
+    draw_poly(center_x, center_y, 4, 15.0 * scale, 45.0, item_color(item));
+    if palette.marks_uniques() && item.is_unique() {
+        draw_unique_mark(center_x, center_y, 15.0 * scale);
+    }
+    let durability_y = y + size - 7.0 * scale;
+    draw_durability_bar(x + 4.0 * scale, durability_y, size - 8.0 * scale, 3.0 * scale, item.durability(), palette);
+}
+
+/// Stat used to compare an item against what's equipped, with a label for the tooltip
+fn comparison_stat(item: &Item) -> (f32, &'static str) {
+    match item {
+        Item::Weapon(w) => (w.average_damage(), "avg damage"),
+        Item::Armor(a) if a.kind.is_off_hand() => (a.kind.block_chance() * 100.0, "% block"),
+        Item::Armor(a) => (a.reduction_for(DamageType::Physical) as f32, "reduction"),
+    }
 }
 
-fn draw_tooltip(x: f32, y: f32, item: &Item) {
-    let name = item.name();
+/// Item details beside (x, y), compared against what `player` has equipped. `trade` is the
+/// vendor's price line, only passed while shopping so the backpack tooltip stays short.
+pub fn draw_tooltip(x: f32, y: f32, item: &Item, player: &Player, trade: Option<&str>, palette: Palette, scale: f32) {
+    let equipped = player.equipped_for(item);
+    let equipped = equipped.as_ref();
+    let warning = player.forced_unequip(item).map(|name| format!("Equipping unequips your {}", name));
+    let warning = warning.as_deref();
+    let player_level = player.level;
+    // Spelled out too, so rarity doesn't rest on the name color alone
+    let name = if palette.marks_uniques() && item.is_unique() {
+        format!("{} (unique)", item.name())
+    } else {
+        item.name().to_string()
+    };
+    let name = name.as_str();
     let desc = item.description();
+    let required = item.required_level();
+    let requirement = (required > 0).then(|| format!("Requires level {}", required));
+
+    // Compare against the equipped item in the same slot; an empty slot counts as zero
+    let (value, label) = comparison_stat(item);
+    let equipped_value = equipped.map(|e| comparison_stat(e).0).unwrap_or(0.0);
+    let delta = value - equipped_value;
+    let equipped_name = equipped.map(|e| e.name()).unwrap_or("nothing");
+    let (compare, compare_color) = if delta > 0.0 {
+        (format!("+{} {} vs {}", format_stat(delta), label, equipped_name), palette.good())
+    } else if delta < 0.0 {
+        (format!("-{} {} vs {}", format_stat(-delta), label, equipped_name), palette.bad())
+    } else {
+        (format!("= {} vs {}", label, equipped_name), GRAY)
+    };
 
-    let padding = 8.0;
-    let name_size = 18.0;
-    let desc_size = 14.0;
+    let padding = 8.0 * scale;
+    let name_size = 18.0 * scale;
+    let desc_size = 14.0 * scale;
 
     let name_dims = measure_text(name, None, name_size as u16, 1.0);
     let desc_dims = measure_text(&desc, None, desc_size as u16, 1.0);
-
-    let tooltip_w = name_dims.width.max(desc_dims.width) + padding * 2.0;
-    let tooltip_h = name_size + desc_size + padding * 2.0;
+    let compare_dims = measure_text(&compare, None, desc_size as u16, 1.0);
+    let warning_w = warning.map_or(0.0, |w| measure_text(w, None, desc_size as u16, 1.0).width);
+    let requirement_w = requirement.as_ref().map_or(0.0, |r| measure_text(r, None, desc_size as u16, 1.0).width);
+    let trade_w = trade.map_or(0.0, |t| measure_text(t, None, desc_size as u16, 1.0).width);
+
+    let tooltip_w = name_dims
+        .width
+        .max(desc_dims.width)
+        .max(compare_dims.width)
+        .max(warning_w)
+        .max(requirement_w)
+        .max(trade_w)
+        + padding * 2.0;
+    let line_h = desc_size + 4.0 * scale;
+    let requirement_h = if requirement.is_some() { line_h } else { 0.0 };
+    let warning_h = if warning.is_some() { line_h } else { 0.0 };
+    let trade_h = if trade.is_some() { line_h } else { 0.0 };
+    let tooltip_h = name_size + desc_size * 3.0 + padding * 2.0 + 8.0 * scale + requirement_h + warning_h + trade_h;
 
     // Ensure tooltip stays on screen
     let screen_w = screen_width();
@@ -266,6 +727,7 @@ fn draw_tooltip(x: f32, y: f32, item: &Item) {
     } else {
         x
     };
+    let y = y.min(screen_height() - tooltip_h).max(0.0);
 
     // Background
     draw_rectangle(
@@ -278,11 +740,7 @@ fn draw_tooltip(x: f32, y: f32, item: &Item) {
     draw_rectangle_lines(actual_x, y, tooltip_w, tooltip_h, 1.0, WHITE);
 
     // Name
-    let name_color = match item {
-        Item::Weapon(_) => ORANGE,
-        Item::Armor(_) => SKYBLUE,
-    };
-    draw_text(name, actual_x + padding, y + padding + name_size - 4.0, name_size, name_color);
+    draw_text(name, actual_x + padding, y + padding + name_size - 4.0 * scale, name_size, item_color(item));
 
     // Description
     draw_text(
@@ -292,4 +750,69 @@ fn draw_tooltip(x: f32, y: f32, item: &Item) {
         desc_size,
         LIGHTGRAY,
     );
+
+    // Comparison with equipped item
+    draw_text(
+        &compare,
+        actual_x + padding,
+        y + padding + name_size + desc_size * 2.0 + 4.0 * scale,
+        desc_size,
+        compare_color,
+    );
+
+    // Durability
+    let (durability, max_durability) = item.durability();
+    let durability_y = y + padding + name_size + desc_size * 3.0 + 8.0 * scale;
+    draw_text(
+        &format!("Durability: {}/{}", durability, max_durability),
+        actual_x + padding,
+        durability_y,
+        desc_size,
+        LIGHTGRAY,
+    );
+    let label_w = measure_text("Durability: 000/000 ", None, desc_size as u16, 1.0).width;
+    let bar_w = (tooltip_w - label_w - padding * 2.0).max(20.0 * scale);
+    let (bar_x, bar_y) = (actual_x + padding + label_w, durability_y - 7.0 * scale);
+    draw_durability_bar(bar_x, bar_y, bar_w, 5.0 * scale, (durability, max_durability), palette);
+
+    // Level requirement, red while the player is too low to equip it
+    let mut line_y = durability_y + line_h;
+    if let Some(requirement) = &requirement {
+        let color = if player_level >= required { GRAY } else { palette.bad() };
+        draw_text(requirement, actual_x + padding, line_y, desc_size, color);
+        line_y += line_h;
+    }
+
+    // Hand conflict (two-hander vs shield)
+    if let Some(warning) = warning {
+        draw_text(warning, actual_x + padding, line_y, desc_size, ORANGE);
+        line_y += line_h;
+    }
+
+    if let Some(trade) = trade {
+        draw_text(trade, actual_x + padding, line_y, desc_size, GOLD);
+    }
+}
+
+/// Thin wear bar: good when fresh, warning when worn, bad when close to breaking
+fn draw_durability_bar(x: f32, y: f32, width: f32, height: f32, (current, max): (u32, u32), palette: Palette) {
+    let pct = if max == 0 { 0.0 } else { current as f32 / max as f32 };
+    let color = if pct > 0.5 {
+        palette.good()
+    } else if pct > 0.2 {
+        palette.warning()
+    } else {
+        palette.bad()
+    };
+    draw_rectangle(x, y, width, height, DARKGRAY);
+    draw_rectangle(x, y, width * pct, height, color);
+}
+
+/// Whole numbers without a trailing ".0", halves with one decimal
+fn format_stat(value: f32) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i32)
+    } else {
+        format!("{:.1}", value)
+    }
 }
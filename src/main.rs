@@ -1,28 +1,62 @@
 use macroquad::prelude::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 
+mod balance;
 mod camera;
 mod combat;
+mod difficulty;
+mod hazard;
 mod inventory;
 mod monsters;
+mod particles;
 mod player;
+mod quest;
+mod rng;
+mod save;
+mod settings;
+mod spawner;
 mod ui;
+mod vendor;
 mod world;
 
 use camera::GameCamera;
-use monsters::{Monster, MonsterType};
-use player::Player;
-use world::World;
-
+use combat::{DamageType, Item, Weapon};
+use difficulty::Difficulty;
+use hazard::Hazard;
+use inventory::{ContextMenu, Corpse, EquipSlot, GroundItem, SlotAction};
+use monsters::{Flock, Monster, MonsterState, Projectile};
+use particles::{Footprints, Particle, Weather};
+use player::{Arrow, Direction, Player, Stat, StartingClass, ThrownWeapon};
+use quest::Quest;
+use rng::GameRng;
+use settings::{Setting, Settings};
+use spawner::Spawner;
+use vendor::{ShopClick, Vendor};
+use world::{Decoration, Terrain, World};
+
+#[derive(Clone, Copy)]
 pub enum GameState {
+    MainMenu,
     Playing,
     Inventory,
+    Paused,
+    LevelUp,
+    Shop,
     GameOver,
+    Settings,
+}
+
+/// Whose health a damage number came off, so quick repeat hits on one target add up
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DamageTarget {
+    Player,
+    Monster(u32), // Monster::id
 }
 
 /// Floating text that rises and fades out
 pub struct FloatingText {
     text: String,
+    damage: Option<(DamageTarget, i32)>, // Set for damage numbers: the target and the running total
     world_x: f32,
     world_y: f32,
     offset_y: f32,    // Vertical offset that increases over time
@@ -34,6 +68,7 @@ impl FloatingText {
     pub fn new(text: String, world_x: f32, world_y: f32) -> Self {
         Self {
             text,
+            damage: None,
             world_x,
             world_y,
             offset_y: 0.0,
@@ -42,6 +77,36 @@ impl FloatingText {
         }
     }
 
+    const MERGE_WINDOW: f32 = 0.6; // Seconds after a damage number's last hit that another adds onto it
+
+    fn damage_number(target: DamageTarget, amount: i32, world_x: f32, world_y: f32) -> Self {
+        let mut text = Self::new(String::new(), world_x, world_y);
+        text.damage = Some((target, 0));
+        text.add_damage(amount, world_x, world_y);
+        text
+    }
+
+    /// Still taking hits on `target`
+    fn merges_with(&self, target: DamageTarget) -> bool {
+        self.damage.is_some_and(|(t, _)| t == target) && self.max_lifetime - self.lifetime < Self::MERGE_WINDOW
+    }
+
+    /// Add a hit to the total, restarting the text over the target's current spot
+    fn add_damage(&mut self, amount: i32, world_x: f32, world_y: f32) {
+        let Some((target, total)) = &mut self.damage else {
+            return;
+        };
+        *total += amount;
+        self.text = match target {
+            DamageTarget::Player => format!("-{}", total),
+            DamageTarget::Monster(_) => total.to_string(),
+        };
+        self.world_x = world_x;
+        self.world_y = world_y;
+        self.offset_y = 0.0;
+        self.lifetime = self.max_lifetime;
+    }
+
     pub fn update(&mut self, dt: f32) {
         self.lifetime -= dt;
         self.offset_y += 30.0 * dt; // Rise upward
@@ -51,12 +116,12 @@ impl FloatingText {
         self.lifetime <= 0.0
     }
 
-    pub fn draw(&self, camera: &GameCamera) {
+    pub fn draw(&self, camera: &GameCamera, scale: f32) {
         let (screen_x, screen_y) = camera.world_to_screen(self.world_x, self.world_y);
         let alpha = (self.lifetime / self.max_lifetime * 255.0) as u8;
 
         let color = Color::from_rgba(255, 255, 100, alpha); // Yellow text
-        let font_size = 18.0;
+        let font_size = 18.0 * scale;
 
         let text_dims = measure_text(&self.text, None, font_size as u16, 1.0);
         draw_text(
@@ -69,35 +134,168 @@ impl FloatingText {
     }
 }
 
+/// An entity queued for depth-sorted drawing
+enum DrawCommand<'a> {
+    Decoration(f32, f32, Decoration),
+    GroundItem(&'a GroundItem),
+    Monster(&'a Monster),
+    Vendor(&'a Vendor),
+    Corpse(&'a Corpse),
+    Player,
+}
+
+impl DrawCommand<'_> {
+    /// Isometric depth: screen y grows with world x + y, so larger values are nearer the viewer
+    fn depth(&self, player: &Player) -> f32 {
+        match self {
+            DrawCommand::Decoration(x, y, _) => x + y,
+            DrawCommand::GroundItem(item) => item.x + item.y,
+            DrawCommand::Monster(monster) => monster.x + monster.y,
+            DrawCommand::Vendor(vendor) => vendor.x + vendor.y,
+            DrawCommand::Corpse(corpse) => corpse.x + corpse.y,
+            DrawCommand::Player => player.x + player.y,
+        }
+    }
+}
+
 pub struct Game {
     state: GameState,
     player: Player,
     world: World,
     camera: GameCamera,
     monsters: Vec<Monster>,
-    ground_items: Vec<inventory::GroundItem>,
+    ground_items: Vec<GroundItem>,
+    projectiles: Vec<Projectile>,
+    thrown_weapons: Vec<ThrownWeapon>,
+    arrows: Vec<Arrow>,
+    hazards: Vec<Hazard>,
+    particles: Vec<Particle>, // Oldest first
+    footprints: Footprints,   // Prints left on snow
+    weather: Weather,
+    hazard_timer: f32, // Time until the next sandstorm roll
+    corpse: Option<Corpse>, // At most one - dying again replaces it
     spawned_chunks: HashSet<(i32, i32)>,
-    floating_texts: Vec<FloatingText>,
+    next_monster_id: u32,
+    chunk_states: HashMap<(i32, i32), Vec<MonsterState>>, // Survivors of unloaded chunks
+    spawner: Spawner, // First visits to a chunk are rolled off the main thread
+    floating_texts: VecDeque<FloatingText>, // Oldest at the front
+    rng: GameRng,
+    vendor: Vendor,
+    was_at_vendor: bool, // Shop opens on stepping onto the vendor, not while standing there
+    targeted_monster: Option<usize>,
+    attack_move: Option<(f32, f32)>, // Destination of a shift-click: walk there, fighting anything in reach
+    waypoint: Option<(f32, f32)>,
+    quest: Quest,
+    inventory_page: usize,
+    dragging: Option<usize>,              // Backpack slot being dragged
+    selected_slot: usize,                 // Keyboard cursor in the backpack (absolute index)
+    context_menu: Option<ContextMenu>,    // Right-click menu on a backpack slot
+    last_slot_click: Option<(usize, f64)>, // (slot, time) of the last click, for double-click equip
+    debug: bool,
+    seed: u32,
+    difficulty: Difficulty, // Also the main-menu choice for the next new game
+    class: StartingClass,   // Main-menu choice for the next new game
+    seed_input: String, // Main-menu seed field; empty or invalid means random
+    show_seed: bool,
+    settings: Settings,
+    settings_selection: usize,
+    settings_return: GameState, // Screen the settings menu goes back to
+    minimap: ui::MinimapCache,
+    ambient_tint: Color, // Eased toward the current terrain's tint so biome edges fade rather than pop
+    hud: ui::HudState,
+    kills: u32,
+    play_time: f64, // Seconds spent in active play over the whole run
+    save_slot: Option<usize>, // Slot this run saves into
+    menu_selection: usize,    // 0 = New Game, 1.. = save slots
+    slot_infos: Vec<Option<save::SlotInfo>>,
 }
 
 impl Game {
-    const CHUNK_SIZE: i32 = 8;
     const SPAWN_RANGE: i32 = 3; // Spawn in chunks within this range
+    const UNLOAD_RANGE: i32 = 5; // Chunks beyond this are unloaded and their monsters stored
+    const AMBIENT_TINT_RATE: f32 = 2.0;
+    const RESPAWN_CLEAR_RADIUS: f32 = 12.0; // Monsters this close to the respawn point back off
+    const FORGET_RANGE: i32 = 12; // Stored chunks beyond this are dropped and respawn fresh
+    const MAX_MONSTERS: usize = 80; // Active monsters; the farthest unseen ones past this are parked
+    const CULL_SCREEN_MARGIN: f32 = 80.0; // Monsters this close to the screen edge count as visible
+    const HAZARD_ROLL_INTERVAL: f32 = 6.0;
+    const HAZARD_CHANCE: f32 = 0.35; // Per roll, while standing in the desert
+    const MAX_HAZARDS: usize = 2;
+    const DEATH_PARTICLES: usize = 16;
+    const MAX_PARTICLES: usize = 256;
+    const TARGET_RANGE: f32 = 10.0; // Tab-targeting reach in tiles
+    const HEAVY_HIT_DAMAGE: i32 = 8; // Hits at or above this shake the camera
+    const MAX_FLOATING_TEXTS: usize = 64;
+    const DOUBLE_CLICK_TIME: f64 = 0.35;
+    const SEED_MAX_DIGITS: usize = 10; // u32::MAX is ten digits
+    const MANUAL_PICKUP_RANGE: f32 = 1.0; // Manual pickup is deliberate, so it reaches a little further
+    const LOOT_SCATTER_RADIUS: f32 = 0.8; // Under MANUAL_PICKUP_RANGE, so drops are reachable from the body
+    const LOOT_SCATTER_TRIES: usize = 6;
 
     pub fn new() -> Self {
-        let player = Player::new(0.0, 0.0);
-        let world = World::new(12345); // Seed for noise
+        Self::with_seed(save::LEGACY_SEED, Difficulty::Normal, StartingClass::Warrior)
+    }
+
+    /// Fresh game whose terrain, decorations, spawns and loot all follow `seed`
+    fn with_seed(seed: u32, difficulty: Difficulty, class: StartingClass) -> Self {
+        let player = Player::with_class(0.0, 0.0, class);
+        let hud = ui::HudState::new(&player);
+        let world = World::new(seed); // Seed for noise
         let camera = GameCamera::new();
+        let mut rng = GameRng::new(seed as u64); // Loot rolls follow the world seed
+        let vendor = Vendor::new(3.0, -3.0, &mut rng);
+        let quest = Quest::random(&mut rng);
 
         let mut game = Self {
-            state: GameState::Playing,
+            state: GameState::MainMenu,
             player,
             world,
             camera,
             monsters: Vec::new(),
             ground_items: Vec::new(),
+            projectiles: Vec::new(),
+            thrown_weapons: Vec::new(),
+            arrows: Vec::new(),
+            hazards: Vec::new(),
+            particles: Vec::new(),
+            footprints: Footprints::new(),
+            weather: Weather::new(),
+            hazard_timer: Self::HAZARD_ROLL_INTERVAL,
+            corpse: None,
             spawned_chunks: HashSet::new(),
-            floating_texts: Vec::new(),
+            next_monster_id: 0,
+            chunk_states: HashMap::new(),
+            spawner: Spawner::new(seed),
+            floating_texts: VecDeque::new(),
+            rng,
+            vendor,
+            was_at_vendor: false,
+            targeted_monster: None,
+            attack_move: None,
+            waypoint: None,
+            quest,
+            inventory_page: 0,
+            dragging: None,
+            selected_slot: 0,
+            context_menu: None,
+            last_slot_click: None,
+            debug: false,
+            seed,
+            difficulty,
+            class,
+            seed_input: cli_seed().unwrap_or_default(),
+            show_seed: false,
+            settings: Settings::load(),
+            settings_selection: 0,
+            settings_return: GameState::MainMenu,
+            minimap: ui::MinimapCache::new(),
+            ambient_tint: Terrain::Grass.ambient_tint(),
+            hud,
+            kills: 0,
+            play_time: 0.0,
+            save_slot: None,
+            menu_selection: 0,
+            slot_infos: (0..save::SLOT_COUNT).map(save::slot_info).collect(),
         };
 
         // Initial monster spawn around player
@@ -106,8 +304,11 @@ impl Game {
     }
 
     fn spawn_monsters_around_player(&mut self) {
-        let player_chunk_x = (self.player.x / Self::CHUNK_SIZE as f32).floor() as i32;
-        let player_chunk_y = (self.player.y / Self::CHUNK_SIZE as f32).floor() as i32;
+        for spawns in self.spawner.collect_ready() {
+            self.add_monsters(spawns.monsters);
+        }
+
+        let (player_chunk_x, player_chunk_y) = Self::chunk_of(self.player.x, self.player.y);
 
         for cy in (player_chunk_y - Self::SPAWN_RANGE)..=(player_chunk_y + Self::SPAWN_RANGE) {
             for cx in (player_chunk_x - Self::SPAWN_RANGE)..=(player_chunk_x + Self::SPAWN_RANGE) {
@@ -116,79 +317,356 @@ impl Game {
         }
     }
 
-    fn spawn_chunk(&mut self, chunk_x: i32, chunk_y: i32) {
-        if self.spawned_chunks.contains(&(chunk_x, chunk_y)) {
-            return;
+    fn chunk_of(x: f32, y: f32) -> (i32, i32) {
+        (
+            (x / spawner::CHUNK_SIZE as f32).floor() as i32,
+            (y / spawner::CHUNK_SIZE as f32).floor() as i32,
+        )
+    }
+
+    /// Unload far chunks, keeping their monsters' state, and forget state that's very far away
+    fn unload_distant_chunks(&mut self) {
+        let (player_cx, player_cy) = Self::chunk_of(self.player.x, self.player.y);
+        let chunk_distance = |(cx, cy): (i32, i32)| (cx - player_cx).abs().max((cy - player_cy).abs());
+
+        let far_chunks: Vec<(i32, i32)> = self
+            .spawned_chunks
+            .iter()
+            .copied()
+            // A chunk still being rolled waits for its monsters before it can be stored away
+            .filter(|&chunk| chunk_distance(chunk) > Self::UNLOAD_RANGE && !self.spawner.is_pending(chunk))
+            .collect();
+        for chunk in far_chunks {
+            self.spawned_chunks.remove(&chunk);
+
+            // Monsters belong to the chunk they spawned in, wherever they've wandered
+            let mut states = Vec::new();
+            for i in (0..self.monsters.len()).rev() {
+                let monster = &self.monsters[i];
+                if Self::chunk_of(monster.home_x, monster.home_y) == chunk {
+                    states.push(self.remove_monster(i).to_state());
+                }
+            }
+            // Stored even when empty so a cleared chunk stays cleared; added to anything parked there by the cap
+            self.chunk_states.entry(chunk).or_default().extend(states);
         }
-        self.spawned_chunks.insert((chunk_x, chunk_y));
 
-        // Use deterministic random based on chunk coords
-        let hash = ((chunk_x.wrapping_mul(374761393)) ^ (chunk_y.wrapping_mul(668265263))) as u32;
+        self.chunk_states.retain(|&chunk, _| chunk_distance(chunk) <= Self::FORGET_RANGE);
+    }
 
-        // ~20% chance to spawn a monster in this chunk
-        if hash % 5 != 0 {
+    /// Over MAX_MONSTERS, park the farthest off-screen monsters in their home chunk's stored state as if
+    /// it had unloaded, so they return when that chunk streams back in. Visible monsters are never
+    /// parked; if they alone are over the cap, it's exceeded until some wander off or die.
+    fn enforce_monster_cap(&mut self) {
+        let excess = self.monsters.len().saturating_sub(Self::MAX_MONSTERS);
+        if excess == 0 {
             return;
         }
+        let (player_x, player_y) = (self.player.x, self.player.y);
+        let mut unseen: Vec<(usize, f32)> = self
+            .monsters
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| !self.camera.is_on_screen(m.x, m.y, Self::CULL_SCREEN_MARGIN))
+            .map(|(i, m)| (i, (m.x - player_x).powi(2) + (m.y - player_y).powi(2)))
+            .collect();
+        unseen.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut parked: Vec<usize> = unseen.into_iter().take(excess).map(|(i, _)| i).collect();
+        parked.sort_unstable_by(|a, b| b.cmp(a)); // Highest first so earlier indices stay valid
+        for i in parked {
+            let monster = self.remove_monster(i);
+            let chunk = Self::chunk_of(monster.home_x, monster.home_y);
+            self.chunk_states.entry(chunk).or_default().push(monster.to_state());
+        }
+    }
 
-        // Get center of chunk
-        let world_x = (chunk_x * Self::CHUNK_SIZE) as f32 + (Self::CHUNK_SIZE as f32 / 2.0);
-        let world_y = (chunk_y * Self::CHUNK_SIZE) as f32 + (Self::CHUNK_SIZE as f32 / 2.0);
-
-        // Add some randomness to position within chunk
-        let offset_x = ((hash >> 8) % (Self::CHUNK_SIZE as u32)) as f32 - (Self::CHUNK_SIZE as f32 / 2.0);
-        let offset_y = ((hash >> 16) % (Self::CHUNK_SIZE as u32)) as f32 - (Self::CHUNK_SIZE as f32 / 2.0);
-
-        let spawn_x = world_x + offset_x;
-        let spawn_y = world_y + offset_y;
+    fn spawn_chunk(&mut self, chunk_x: i32, chunk_y: i32) {
+        if self.spawned_chunks.contains(&(chunk_x, chunk_y)) {
+            return;
+        }
+        self.spawned_chunks.insert((chunk_x, chunk_y));
 
-        // Don't spawn too close to player start
-        if spawn_x.abs() < 5.0 && spawn_y.abs() < 5.0 {
+        // Revisiting an unloaded chunk restores what was left there
+        if let Some(states) = self.chunk_states.remove(&(chunk_x, chunk_y)) {
+            let difficulty = self.difficulty;
+            self.add_monsters(states.iter().map(|state| Monster::from_state(state, difficulty)).collect());
             return;
         }
 
-        // Get terrain and spawn appropriate monster
-        let terrain = self.world.get_terrain_at(spawn_x, spawn_y);
-        let monster_type = MonsterType::random_for_terrain(terrain);
+        self.spawner.request((chunk_x, chunk_y), self.difficulty);
+    }
 
-        self.monsters.push(Monster::new(spawn_x, spawn_y, monster_type));
+    /// Bring monsters into play, each with a fresh id
+    fn add_monsters(&mut self, monsters: Vec<Monster>) {
+        for mut monster in monsters {
+            monster.id = self.next_monster_id;
+            self.next_monster_id = self.next_monster_id.wrapping_add(1);
+            self.monsters.push(monster);
+        }
     }
 
     pub fn update(&mut self) {
+        // Debug overlay toggle works in every state
+        if is_key_pressed(KeyCode::F3) {
+            self.debug = !self.debug;
+        }
+        if is_key_pressed(KeyCode::F4) {
+            self.show_seed = !self.show_seed;
+        }
+        self.hud.update(get_frame_time(), &self.player);
+
         match self.state {
+            GameState::MainMenu => self.update_main_menu(),
             GameState::Playing => self.update_playing(),
             GameState::Inventory => self.update_inventory(),
+            GameState::Paused => self.update_paused(),
+            GameState::LevelUp => self.update_level_up(),
+            GameState::Shop => self.update_shop(),
             GameState::GameOver => self.update_game_over(),
+            GameState::Settings => self.update_settings(),
+        }
+        // After the state update so a respawn or camera snap is baked before this frame draws
+        self.world.refresh_terrain_mesh(&self.camera);
+    }
+
+    fn update_main_menu(&mut self) {
+        let rows = save::SLOT_COUNT + 2; // New Game, the slots, then Settings
+        if is_key_pressed(KeyCode::Down) || is_key_pressed(KeyCode::S) {
+            self.menu_selection = (self.menu_selection + 1) % rows;
+        }
+        if is_key_pressed(KeyCode::Up) || is_key_pressed(KeyCode::W) {
+            self.menu_selection = (self.menu_selection + rows - 1) % rows;
+        }
+
+        // Typing edits the seed field while New Game is highlighted
+        while let Some(c) = get_char_pressed() {
+            if self.menu_selection == 0 && c.is_ascii_digit() && self.seed_input.len() < Self::SEED_MAX_DIGITS {
+                self.seed_input.push(c);
+            }
+        }
+        if self.menu_selection == 0 {
+            if is_key_pressed(KeyCode::Backspace) {
+                self.seed_input.pop();
+            }
+            if is_key_pressed(KeyCode::Left) || is_key_pressed(KeyCode::A) {
+                self.difficulty = self.difficulty.cycle(-1);
+            }
+            if is_key_pressed(KeyCode::Right) || is_key_pressed(KeyCode::D) {
+                self.difficulty = self.difficulty.cycle(1);
+            }
+            if is_key_pressed(KeyCode::Q) {
+                self.class = self.class.cycle(-1);
+            }
+            if is_key_pressed(KeyCode::E) {
+                self.class = self.class.cycle(1);
+            }
+        }
+
+        // Clear the highlighted slot
+        if is_key_pressed(KeyCode::Delete) && (1..=save::SLOT_COUNT).contains(&self.menu_selection) {
+            let slot = self.menu_selection - 1;
+            if save::delete_slot(slot).is_ok() {
+                self.slot_infos[slot] = None;
+            }
+        }
+
+        if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space) {
+            match self.menu_selection {
+                // New Game saves into the first empty slot, if any
+                0 => self.start_game(self.slot_infos.iter().position(Option::is_none)),
+                n if n <= save::SLOT_COUNT => self.start_game(Some(n - 1)),
+                _ => self.open_settings(),
+            }
+        }
+    }
+
+    /// Load `slot` if it holds a save, otherwise start a fresh run bound to it
+    fn start_game(&mut self, slot: Option<usize>) {
+        // A loaded run keeps its own world and difficulty; a new one uses the menu choices
+        let data = slot.and_then(save::load_game);
+        let (seed, difficulty) = match &data {
+            Some(data) => (data.seed, data.difficulty),
+            None => (self.seed_input.trim().parse().unwrap_or_else(|_| random_seed()), self.difficulty),
+        };
+        // The class only matters for a new run; a loaded one brings its own player
+        *self = Game::with_seed(seed, difficulty, self.class);
+        self.save_slot = slot;
+        self.state = GameState::Playing;
+
+        if let Some(data) = data {
+            self.player = data.player;
+            self.kills = data.kills;
+            self.play_time = data.play_time;
+            self.waypoint = data.waypoint;
+            self.corpse = data.corpse;
+            // Saves from before quests get the freshly issued one
+            if let Some(quest) = data.quest {
+                self.quest = quest;
+            }
+            self.hud = ui::HudState::new(&self.player);
+            self.camera.x = self.player.x;
+            self.camera.y = self.player.y;
+            self.was_at_vendor = self.vendor.is_player_on_tile(&self.player);
+            self.unload_distant_chunks();
+            self.spawn_monsters_around_player();
         }
     }
 
+    fn save_current(&mut self) {
+        self.recall_thrown_weapons();
+        let message = match self.save_slot {
+            Some(slot) => match save::save_game(slot, self.seed, self.difficulty, &self.player, self.kills, self.play_time, self.waypoint, self.corpse.as_ref(), &self.quest) {
+                Ok(()) => format!("Saved to slot {}", slot + 1),
+                Err(_) => "Save failed!".to_string(),
+            },
+            None => "No free save slot".to_string(),
+        };
+        self.push_floating_text(message, self.player.x, self.player.y);
+    }
+
     fn update_playing(&mut self) {
+        if is_key_pressed(KeyCode::F5) {
+            self.save_current();
+        }
+
         // Toggle inventory
         if is_key_pressed(KeyCode::I) {
             self.state = GameState::Inventory;
             return;
         }
 
+        // Pause (Escape only pauses from play; inside the inventory it closes the inventory)
+        if is_key_pressed(KeyCode::P) || is_key_pressed(KeyCode::Escape) {
+            self.state = GameState::Paused;
+            return;
+        }
+
+        // Cycle keyboard target
+        if is_key_pressed(KeyCode::Tab) {
+            self.cycle_target();
+        }
+
+        // Drop a waypoint here, or clear the existing one
+        if is_key_pressed(KeyCode::T) {
+            self.waypoint = match self.waypoint {
+                Some(_) => None,
+                None => Some((self.player.x, self.player.y)),
+            };
+        }
+
+        // Throw the equipped weapon toward the cursor
+        if is_key_pressed(KeyCode::R) {
+            let (mouse_x, mouse_y) = mouse_position();
+            let (target_x, target_y) = self.camera.screen_to_world(mouse_x, mouse_y);
+            if let Some(thrown) = self.player.throw_weapon(target_x, target_y, &mut self.rng) {
+                self.thrown_weapons.push(thrown);
+            }
+        }
+
+        // Channel a recall to town
+        if is_key_pressed(KeyCode::H) {
+            if world::is_in_town(self.player.x, self.player.y) {
+                self.push_floating_text("Already in town".to_string(), self.player.x, self.player.y);
+            } else if self.player.recall_cooldown > 0.0 {
+                let text = format!("Recall ready in {:.0}s", self.player.recall_cooldown.ceil());
+                self.push_floating_text(text, self.player.x, self.player.y);
+            } else {
+                self.player.start_recall();
+            }
+        }
+
+        // Shift-click orders an attack-move; steering by hand takes back control
+        if is_mouse_button_pressed(MouseButton::Left) && is_key_down(KeyCode::LeftShift) {
+            let (mouse_x, mouse_y) = mouse_position();
+            self.attack_move = Some(self.camera.screen_to_world(mouse_x, mouse_y));
+        }
+        if player::movement_input() != (0.0, 0.0) {
+            self.attack_move = None;
+        }
+
         let dt = get_frame_time();
+        self.play_time += dt as f64; // Only ticks here, so pauses, menus and overlays don't count
 
         // Update player
         self.player.update(dt, &self.world);
+        self.update_attack_move(dt);
+        if self.player.take_finished_recall() {
+            self.recall_to_town();
+        }
 
-        // Update camera to follow player
-        self.camera.follow(self.player.x, self.player.y, dt);
+        // Update camera to follow player, unless free-look or edge scrolling has the view; letting
+        // go hands it back to the follow lerp, which eases it home. Spawns stay keyed to the player.
+        if is_key_pressed(KeyCode::C) {
+            self.camera.recenter();
+        }
+        let free_look = self.camera.free_look(self.player.x, self.player.y);
+        let edge_scrolling =
+            !free_look && self.settings.edge_scroll && self.camera.edge_scroll(self.player.x, self.player.y, dt);
+        if !free_look && !edge_scrolling {
+            self.camera.follow(self.player.x, self.player.y, dt);
+        }
+        self.camera.update_shake(dt);
+        self.minimap.refresh(&self.world, self.player.x, self.player.y);
+        self.update_ambient_tint(dt);
 
-        // Spawn monsters as player explores
+        // Stream chunks in and out as the player explores
+        self.unload_distant_chunks();
         self.spawn_monsters_around_player();
-
-        // Update monsters
-        for monster in &mut self.monsters {
-            monster.update(dt, self.player.x, self.player.y);
+        self.enforce_monster_cap();
+
+        // Update monsters; ranged ones may fire. Packmates are read before anyone moves.
+        let flocks: Vec<Option<Flock>> = (0..self.monsters.len()).map(|i| Flock::around(&self.monsters, i)).collect();
+        for (monster, flock) in self.monsters.iter_mut().zip(flocks) {
+            monster.update(dt, self.player.x, self.player.y, &self.world, flock);
+            if let Some(projectile) = monster.try_ranged_attack(self.player.x, self.player.y) {
+                self.projectiles.push(projectile);
+            }
         }
 
+        self.update_projectiles(dt);
+        self.update_thrown_weapons(dt);
+        self.update_arrows(dt);
+        self.update_hazards(dt);
+        self.particles.retain_mut(|particle| particle.update(dt));
+        let terrain = self.world.get_terrain_at(self.player.x, self.player.y);
+        self.footprints.update(dt, self.player.x, self.player.y, terrain == Terrain::Snow);
+        self.weather.update(dt, terrain, self.settings.weather);
+
+        // Drop targets that wandered out of range
+        self.validate_target();
+
         // Handle combat
         self.handle_combat();
 
-        // Check for item pickup
+        // Expire old ground loot, then check for item pickup
+        let (player_x, player_y) = (self.player.x, self.player.y);
+        self.ground_items.retain_mut(|item| item.update(dt, player_x, player_y));
+        let reach = self.player.magnet_range(); // Never short of the pickup range
+        for item in &mut self.ground_items {
+            item.release_if_clear(player_x, player_y, reach);
+        }
+        self.pull_ground_items(dt);
         self.check_item_pickup();
+        self.check_corpse_recovery();
+
+        // Stepping onto the vendor opens the shop
+        let at_vendor = self.vendor.is_player_on_tile(&self.player);
+        if at_vendor && !self.was_at_vendor {
+            self.state = GameState::Shop;
+        }
+        self.was_at_vendor = at_vendor;
+
+        // Heavy hits shake the screen, whatever dealt them
+        let largest_hit = self.player.take_largest_hit();
+        if self.settings.screen_shake && largest_hit >= Self::HEAVY_HIT_DAMAGE {
+            self.camera.add_shake(largest_hit as f32 * 0.8);
+        }
+
+        // Warn about gear that broke this frame
+        for name in self.player.take_broken_items() {
+            self.push_floating_text(format!("{} broke!", name), self.player.x, self.player.y);
+        }
 
         // Update floating texts
         for text in &mut self.floating_texts {
@@ -198,101 +676,892 @@ impl Game {
 
         // Check player death
         if self.player.health <= 0 {
-            self.state = GameState::GameOver;
+            self.die();
+        } else if self.player.pending_level_ups > 0 {
+            self.state = GameState::LevelUp;
+        }
+    }
+
+    /// Spend queued level-ups one at a time; play resumes once none are left
+    fn update_level_up(&mut self) {
+        let keys = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4];
+        if let Some(i) = keys.iter().position(|&key| is_key_pressed(key)) {
+            self.player.apply_stat(Stat::ALL[i]);
+        }
+        if self.player.pending_level_ups == 0 {
+            self.state = GameState::Playing;
         }
     }
 
     fn update_inventory(&mut self) {
+        // Escape closes an open menu first; I closes everything
+        if is_key_pressed(KeyCode::Escape) && self.context_menu.take().is_some() {
+            return;
+        }
         if is_key_pressed(KeyCode::I) || is_key_pressed(KeyCode::Escape) {
+            self.state = GameState::Playing;
+            self.dragging = None; // Whatever was held simply stays in its slot
+            self.context_menu = None;
+            return;
+        }
+
+        // An open menu takes every click until it closes
+        if self.context_menu.is_some() {
+            self.update_context_menu();
+            return;
+        }
+        if self.dragging.is_none() {
+            self.context_menu = ContextMenu::open_clicked(&self.player, self.inventory_page, self.settings.ui_scale);
+            if self.context_menu.is_some() {
+                return;
+            }
+        }
+
+        // Page through the backpack; the keyboard cursor keeps its spot on the new page
+        let wheel = mouse_wheel().1;
+        if (is_key_pressed(KeyCode::PageDown) || wheel < 0.0) && self.inventory_page + 1 < inventory::PAGE_COUNT {
+            self.inventory_page += 1;
+            self.selected_slot += inventory::SLOTS_PER_PAGE;
+        }
+        if (is_key_pressed(KeyCode::PageUp) || wheel > 0.0) && self.inventory_page > 0 {
+            self.inventory_page -= 1;
+            self.selected_slot -= inventory::SLOTS_PER_PAGE;
+        }
+        self.selected_slot = self.selected_slot.min(inventory::INVENTORY_SIZE - 1);
+
+        self.update_inventory_keyboard();
+
+        if inventory::sort_button_clicked(self.settings.ui_scale) {
+            self.player.inventory.sort();
+            self.last_slot_click = None; // Slots now hold different items
+        }
+
+        if let Some(slot) = inventory::get_clicked_equip_slot(self.settings.ui_scale) {
+            self.unequip(slot);
+        }
+
+        // Pick up an item (slot indices are absolute, not per-page, so drags can cross pages)
+        if let Some(slot_idx) = inventory::get_clicked_slot(self.inventory_page, self.settings.ui_scale)
+            && slot_idx < self.player.inventory.count()
+        {
+            self.dragging = Some(slot_idx);
+        }
+
+        if is_mouse_button_released(MouseButton::Left)
+            && let Some(from) = self.dragging.take()
+        {
+            match inventory::get_hovered_slot(self.inventory_page, self.settings.ui_scale) {
+                // Released where it was picked up: a click, and two quick ones equip
+                Some(to) if to == from => {
+                    let now = get_time();
+                    let is_double = self
+                        .last_slot_click
+                        .is_some_and(|(slot, time)| slot == from && now - time <= Self::DOUBLE_CLICK_TIME);
+                    if is_double {
+                        self.last_slot_click = None;
+                        self.equip_from_backpack(from);
+                    } else {
+                        self.last_slot_click = Some((from, now));
+                    }
+                }
+                Some(to) => self.player.inventory.move_item(from, to),
+                None => {} // Dropped outside the grid: it never left its slot
+            }
+        }
+    }
+
+    /// Arrow keys move the cursor (clamped at the grid edges, flowing across pages), Enter
+    /// equips and X drops. Moving the mouse over a slot takes the cursor with it.
+    fn update_inventory_keyboard(&mut self) {
+        if mouse_delta_position() != Vec2::ZERO
+            && let Some(hovered) = inventory::get_hovered_slot(self.inventory_page, self.settings.ui_scale)
+        {
+            self.selected_slot = hovered;
+        }
+
+        let row_len = inventory::SLOTS_PER_ROW;
+        let slot = self.selected_slot;
+        if is_key_pressed(KeyCode::Left) && !slot.is_multiple_of(row_len) {
+            self.selected_slot -= 1;
+        }
+        if is_key_pressed(KeyCode::Right) && slot % row_len + 1 < row_len && slot + 1 < inventory::INVENTORY_SIZE {
+            self.selected_slot += 1;
+        }
+        if is_key_pressed(KeyCode::Up) && slot >= row_len {
+            self.selected_slot -= row_len;
+        }
+        if is_key_pressed(KeyCode::Down) && slot + row_len < inventory::INVENTORY_SIZE {
+            self.selected_slot += row_len;
+        }
+        self.inventory_page = self.selected_slot / inventory::SLOTS_PER_PAGE;
+
+        if is_key_pressed(KeyCode::Enter) {
+            self.equip_from_backpack(self.selected_slot);
+        } else if is_key_pressed(KeyCode::X) {
+            self.drop_from_backpack(self.selected_slot);
+        }
+    }
+
+    /// Run the clicked action, or close the menu on a click anywhere else. Greyed-out rows ignore clicks.
+    fn update_context_menu(&mut self) {
+        let Some(menu) = self.context_menu else {
+            return;
+        };
+        let scale = self.settings.ui_scale;
+        // The menu's item can't change under it, but don't trust a stale index
+        let Some(item) = self.player.inventory.items.get(menu.slot) else {
+            self.context_menu = None;
+            return;
+        };
+
+        if let Some(action) = menu.clicked_action(scale) {
+            if !action.is_enabled(item) {
+                return;
+            }
+            self.context_menu = None;
+            match action {
+                SlotAction::Equip => self.equip_from_backpack(menu.slot),
+                SlotAction::Drop => self.drop_from_backpack(menu.slot),
+                // Nothing in the backpack enables these yet
+                SlotAction::Use | SlotAction::Split => {}
+            }
+        } else if (is_mouse_button_pressed(MouseButton::Left) || is_mouse_button_pressed(MouseButton::Right))
+            && !menu.contains_mouse(scale)
+        {
+            // Right-clicking another item moves the menu there
+            self.context_menu = ContextMenu::open_clicked(&self.player, self.inventory_page, scale);
+        }
+    }
+
+    fn drop_from_backpack(&mut self, slot_idx: usize) {
+        if let Some(item) = self.player.inventory.remove_item(slot_idx) {
+            self.push_floating_text(format!("Dropped {}", item.name()), self.player.x, self.player.y);
+            self.ground_items.push(GroundItem::dropped(self.player.x, self.player.y, item));
+        }
+    }
+
+    fn equip_from_backpack(&mut self, slot_idx: usize) {
+        if let Some(item) = self.player.inventory.remove_item(slot_idx) {
+            // Equip the item; displaced gear goes back to the backpack, or the ground if it's full.
+            // Too-high-level items go straight back into the slot they came from.
+            let displaced = match self.player.equip_item(item) {
+                Ok(displaced) => displaced,
+                Err(item) => {
+                    let text = format!("Requires level {}", item.required_level());
+                    self.player.inventory.items.insert(slot_idx, item);
+                    self.push_floating_text(text, self.player.x, self.player.y);
+                    return;
+                }
+            };
+            for old_item in displaced {
+                if !self.player.inventory.add_item(old_item.clone()) {
+                    let text = format!("Backpack full - dropped {}", old_item.name());
+                    self.ground_items.push(GroundItem::new(self.player.x, self.player.y, old_item));
+                    self.push_floating_text(text, self.player.x, self.player.y);
+                }
+            }
+        }
+    }
+
+    /// Move equipped gear into the backpack; refused, with a note, when there's no room for it
+    fn unequip(&mut self, slot: EquipSlot) {
+        let name = match slot {
+            EquipSlot::Weapon => self.player.weapon.as_ref().map(|w| w.name()),
+            EquipSlot::Armor => self.player.armor.as_ref().map(|a| a.name()),
+            EquipSlot::OffHand => self.player.off_hand.as_ref().map(|a| a.name()),
+        };
+        let Some(name) = name.map(str::to_string) else {
+            return;
+        };
+        let unequipped = match slot {
+            EquipSlot::Weapon => self.player.unequip_weapon(),
+            EquipSlot::Armor => self.player.unequip_armor(),
+            EquipSlot::OffHand => self.player.unequip_off_hand(),
+        };
+        let text = if unequipped { format!("Unequipped {}", name) } else { "Backpack full".to_string() };
+        self.push_floating_text(text, self.player.x, self.player.y);
+    }
+
+    fn update_shop(&mut self) {
+        if is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::E) {
             self.state = GameState::Playing;
             return;
         }
 
-        // Handle inventory slot clicks for equipping
-        if let Some(slot_idx) = inventory::get_clicked_slot() {
-            if let Some(item) = self.player.inventory.remove_item(slot_idx) {
-                // Equip the item and get back the old equipped item
-                if let Some(old_item) = self.player.equip_item(item) {
-                    // Put old item back in inventory
-                    self.player.inventory.add_item(old_item);
+        match vendor::get_shop_click(&self.vendor, &self.player) {
+            Some(ShopClick::BuyPotion) if self.player.gold >= vendor::POTION_PRICE => {
+                self.player.gold -= vendor::POTION_PRICE;
+                self.player.heal(vendor::POTION_HEAL);
+            }
+            Some(ShopClick::Buy(index)) => {
+                let price = vendor::buy_price(&self.vendor.stock[index]);
+                if self.player.gold >= price && !self.player.inventory.is_full() {
+                    let item = self.vendor.stock.remove(index);
+                    self.player.gold -= price;
+                    self.player.inventory.add_item(item);
                 }
             }
+            Some(ShopClick::Sell(index)) => {
+                if let Some(item) = self.player.inventory.remove_item(index) {
+                    self.player.gold += vendor::sell_price(&item);
+                    self.vendor.take_sold(item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn update_paused(&mut self) {
+        // Only listen for resume/quit - no dt-based logic runs while paused
+        if is_key_pressed(KeyCode::P) || is_key_pressed(KeyCode::Escape) {
+            self.state = GameState::Playing;
+        } else if is_key_pressed(KeyCode::F5) {
+            self.save_current();
+        } else if is_key_pressed(KeyCode::G) {
+            self.toggle_setting(Setting::AutoPickup);
+        } else if is_key_pressed(KeyCode::O) {
+            self.open_settings();
+        } else if is_key_pressed(KeyCode::Q) {
+            *self = Game::new(); // Back to the main menu; unsaved progress is dropped
+        }
+    }
+
+    /// Finish a recall: drop the player in the middle of town with the camera already there
+    fn recall_to_town(&mut self) {
+        self.player.x = 0.0;
+        self.player.y = 0.0;
+        self.camera.x = 0.0;
+        self.camera.y = 0.0;
+        self.camera.recenter();
+        self.targeted_monster = None;
+        self.attack_move = None;
+        self.was_at_vendor = self.vendor.is_player_on_tile(&self.player);
+        self.push_floating_text("Recalled to town".to_string(), 0.0, 0.0);
+    }
+
+    /// Ease the screen tint toward the terrain underfoot over about a second
+    fn update_ambient_tint(&mut self, dt: f32) {
+        let target = self.world.get_terrain_at(self.player.x, self.player.y).ambient_tint();
+        let t = 1.0 - (-Self::AMBIENT_TINT_RATE * dt).exp();
+        let tint = &mut self.ambient_tint;
+        tint.r += (target.r - tint.r) * t;
+        tint.g += (target.g - tint.g) * t;
+        tint.b += (target.b - tint.b) * t;
+        tint.a += (target.a - tint.a) * t;
+    }
+
+    /// Show the settings screen, returning to whichever menu opened it
+    fn open_settings(&mut self) {
+        self.settings_return = self.state;
+        self.settings_selection = 0;
+        self.state = GameState::Settings;
+    }
+
+    fn update_settings(&mut self) {
+        let rows = Setting::ALL.len();
+        if is_key_pressed(KeyCode::Down) || is_key_pressed(KeyCode::S) {
+            self.settings_selection = (self.settings_selection + 1) % rows;
+        }
+        if is_key_pressed(KeyCode::Up) || is_key_pressed(KeyCode::W) {
+            self.settings_selection = (self.settings_selection + rows - 1) % rows;
+        }
+        if [KeyCode::Enter, KeyCode::Space, KeyCode::Left, KeyCode::Right, KeyCode::A, KeyCode::D]
+            .iter()
+            .any(|&key| is_key_pressed(key))
+        {
+            self.toggle_setting(Setting::ALL[self.settings_selection]);
+        }
+        if is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::Backspace) {
+            self.state = self.settings_return;
         }
     }
 
+    /// Flip a setting (live from the next frame) and write the config file straight away
+    fn toggle_setting(&mut self, setting: Setting) {
+        self.settings.toggle(setting);
+        if self.settings.save().is_err() {
+            self.push_floating_text("Couldn't save settings".to_string(), self.player.x, self.player.y);
+        }
+    }
+
+    /// Leave gold and backpack on a corpse where the player fell (or end the run on permadeath tiers)
+    fn die(&mut self) {
+        if self.difficulty.is_permadeath() {
+            // Hell runs end here; the slot goes so the run can't be reloaded
+            if let Some(slot) = self.save_slot.take() {
+                let _ = save::delete_slot(slot);
+            }
+            self.corpse = None;
+            self.state = GameState::GameOver;
+            return;
+        }
+        if self.corpse.as_ref().is_some_and(|c| !c.is_empty()) {
+            self.push_floating_text("Your old corpse crumbles...".to_string(), self.player.x, self.player.y);
+        }
+        self.corpse = Some(Corpse {
+            x: self.player.x,
+            y: self.player.y,
+            gold: std::mem::take(&mut self.player.gold),
+            items: std::mem::take(&mut self.player.inventory.items),
+        });
+        self.state = GameState::GameOver;
+    }
+
+    /// Respawn at the waypoint (or town without one); the penalty is the trip back plus this level's XP progress
+    fn respawn(&mut self) {
+        let (x, y) = self.waypoint.unwrap_or((0.0, 0.0));
+        self.player.respawn_at(x, y);
+        self.player.xp = 0;
+        self.camera.x = x;
+        self.camera.y = y;
+        self.camera.recenter();
+        self.projectiles.clear();
+        self.arrows.clear();
+        self.hazards.clear();
+        self.targeted_monster = None;
+        self.attack_move = None;
+
+        // Nothing gets a free hit on a player who just stood up
+        for monster in &mut self.monsters {
+            let (dx, dy) = (monster.x - x, monster.y - y);
+            if (dx * dx + dy * dy).sqrt() <= Self::RESPAWN_CLEAR_RADIUS {
+                monster.hold_off(Player::SPAWN_PROTECTION);
+            }
+        }
+
+        self.was_at_vendor = self.vendor.is_player_on_tile(&self.player);
+        self.state = GameState::Playing;
+    }
+
     fn update_game_over(&mut self) {
-        if is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Enter) {
-            // Restart game
+        let permadeath = self.difficulty.is_permadeath();
+        if !permadeath && (is_key_pressed(KeyCode::Space) || is_key_pressed(KeyCode::Enter)) {
+            self.respawn();
+        } else if is_key_pressed(KeyCode::Q) || (permadeath && is_key_pressed(KeyCode::Enter)) {
+            // Back to the main menu to start over or load a save
             *self = Game::new();
         }
     }
 
-    fn handle_combat(&mut self) {
-        // Player attacking monsters
-        if is_mouse_button_pressed(MouseButton::Left) && self.player.can_attack() {
-            self.player.attack();
+    /// Queue a floating text, dropping the oldest once the cap is hit so new feedback always shows
+    fn push_floating_text(&mut self, text: String, world_x: f32, world_y: f32) {
+        self.push_floating(FloatingText::new(text, world_x, world_y));
+    }
 
-            let attack_range = 1.0; // 1 tile
-            let mut dead_indices = Vec::new();
+    /// Damage number over a target; hits that land while its last number is fresh add onto that one
+    fn push_damage_number(&mut self, target: DamageTarget, amount: i32, world_x: f32, world_y: f32) {
+        match self.floating_texts.iter_mut().rev().find(|t| t.merges_with(target)) {
+            Some(text) => text.add_damage(amount, world_x, world_y),
+            None => self.push_floating(FloatingText::damage_number(target, amount, world_x, world_y)),
+        }
+    }
 
-            for (i, monster) in self.monsters.iter_mut().enumerate() {
-                let dx = monster.x - self.player.x;
-                let dy = monster.y - self.player.y;
-                let dist = (dx * dx + dy * dy).sqrt();
+    fn push_floating(&mut self, text: FloatingText) {
+        push_bounded(&mut self.floating_texts, text, Self::MAX_FLOATING_TEXTS);
+    }
 
-                if dist <= attack_range {
-                    let damage = self.player.calculate_damage();
-                    monster.take_damage(damage);
+    fn distance_to_player(&self, monster: &Monster) -> f32 {
+        let dx = monster.x - self.player.x;
+        let dy = monster.y - self.player.y;
+        (dx * dx + dy * dy).sqrt()
+    }
 
-                    if monster.health <= 0 {
-                        dead_indices.push(i);
-                    }
-                }
+    /// Distance from the player to the edge of the monster's body
+    fn edge_distance_to_player(&self, monster: &Monster) -> f32 {
+        (self.distance_to_player(monster) - monster.monster_type.footprint_radius()).max(0.0)
+    }
+
+    /// Select the next monster in range, ordered nearest-first (ties broken by index)
+    fn cycle_target(&mut self) {
+        let mut candidates: Vec<(usize, f32)> = self
+            .monsters
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (i, self.distance_to_player(m)))
+            .filter(|&(_, dist)| dist <= Self::TARGET_RANGE)
+            .collect();
+        candidates.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+
+        if candidates.is_empty() {
+            self.targeted_monster = None;
+            return;
+        }
+
+        let next = match self
+            .targeted_monster
+            .and_then(|t| candidates.iter().position(|&(i, _)| i == t))
+        {
+            Some(pos) => (pos + 1) % candidates.len(),
+            None => 0,
+        };
+        self.targeted_monster = Some(candidates[next].0);
+    }
+
+    fn validate_target(&mut self) {
+        if let Some(t) = self.targeted_monster
+            && self
+                .monsters
+                .get(t)
+                .is_none_or(|m| self.distance_to_player(m) > Self::TARGET_RANGE)
+        {
+            self.targeted_monster = None;
+        }
+    }
+
+    /// Remove a monster, keeping the target index pointing at the same monster
+    fn remove_monster(&mut self, index: usize) -> Monster {
+        self.targeted_monster = match self.targeted_monster {
+            Some(t) if t == index => None,
+            Some(t) if t > index => Some(t - 1),
+            other => other,
+        };
+        self.monsters.remove(index)
+    }
+
+    /// Add particles, dropping the oldest past the cap
+    fn spawn_particles(&mut self, particles: impl Iterator<Item = Particle>) {
+        self.particles.extend(particles);
+        let excess = self.particles.len().saturating_sub(Self::MAX_PARTICLES);
+        self.particles.drain(..excess);
+    }
+
+    /// Remove a dead monster and pay out its gold, XP and loot
+    fn kill_monster(&mut self, index: usize) {
+        let monster = self.remove_monster(index);
+        self.kills += 1;
+        self.spawn_particles(particles::death_burst(
+            monster.x,
+            monster.y,
+            monster.monster_type.color(),
+            Self::DEATH_PARTICLES,
+        ));
+
+        let gold = monster.roll_gold(&mut self.rng);
+        self.player.gold += gold;
+        let xp = monster.xp_reward();
+        self.player.gain_xp(xp);
+        self.push_floating_text(format!("+{} gold, +{} XP", gold, xp), monster.x, monster.y);
+
+        if let Some(item) = monster.roll_loot(&mut self.rng) {
+            let (x, y) = self.scatter_drop(monster.x, monster.y);
+            self.ground_items.push(GroundItem::new(x, y, item));
+        }
+
+        if self.quest.record_kill(monster.monster_type) {
+            self.complete_quest();
+        }
+    }
+
+    /// A spot near a death for its loot, so a pack's drops don't stack on one point. Stays inside
+    /// manual pickup reach of the body and off blocked tiles, falling back to the body itself.
+    /// Cosmetic, so it rolls from macroquad's rng and leaves the seeded loot sequence alone.
+    fn scatter_drop(&self, x: f32, y: f32) -> (f32, f32) {
+        for _ in 0..Self::LOOT_SCATTER_TRIES {
+            let angle = rand::gen_range(0.0, std::f32::consts::TAU);
+            let distance = rand::gen_range(0.0, Self::LOOT_SCATTER_RADIUS);
+            let (drop_x, drop_y) = (x + angle.cos() * distance, y + angle.sin() * distance);
+            if !self.world.is_blocked(drop_x, drop_y) {
+                return (drop_x, drop_y);
             }
+        }
+        (x, y)
+    }
+
+    /// Pay out the finished quest and issue the next one; a reward item that doesn't fit drops at the player's feet
+    fn complete_quest(&mut self) {
+        let (gold, xp) = (self.quest.gold_reward(), self.quest.xp_reward());
+        self.player.gold += gold;
+        self.player.gain_xp(xp);
+        let item = Item::random(&mut self.rng);
+        let item_name = item.name().to_string();
+        if !self.player.inventory.add_item(item.clone()) {
+            self.ground_items.push(GroundItem::new(self.player.x, self.player.y, item));
+        }
+        self.push_floating_text(format!("Quest complete! +{} gold, +{} XP, {}", gold, xp, item_name), self.player.x, self.player.y);
 
-            // Remove dead monsters and spawn loot
-            for i in dead_indices.into_iter().rev() {
-                let monster = self.monsters.remove(i);
-                if let Some(item) = monster.roll_loot() {
-                    self.ground_items.push(inventory::GroundItem {
-                        x: monster.x,
-                        y: monster.y,
-                        item,
-                    });
+        self.quest = Quest::random(&mut self.rng);
+        self.push_floating_text(format!("New quest: {}", self.quest.description()), self.player.x, self.player.y - 0.5);
+    }
+
+    /// Follow an attack-move order: halt for the nearest monster in reach and swing whenever the
+    /// cooldown allows, then carry on toward the destination once nothing is left in reach
+    fn update_attack_move(&mut self, dt: f32) {
+        let Some((x, y)) = self.attack_move else {
+            return;
+        };
+        let nearest = (0..self.monsters.len())
+            .map(|i| (i, self.edge_distance_to_player(&self.monsters[i])))
+            .filter(|&(_, dist)| dist <= self.player.attack_reach())
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        match nearest {
+            Some((i, _)) => {
+                let (monster_x, monster_y) = (self.monsters[i].x, self.monsters[i].y);
+                self.player.facing = Direction::from_vector(monster_x - self.player.x, monster_y - self.player.y);
+                if self.player.can_attack() {
+                    self.swing(monster_x, monster_y);
+                }
+            }
+            None => {
+                if self.player.walk_toward(x, y, dt, &self.world) {
+                    self.attack_move = None;
                 }
             }
         }
+    }
+
+    /// Attack toward a world point: a bow looses an arrow at it, anything else swings at what's in the arc
+    fn swing(&mut self, target_x: f32, target_y: f32) {
+        if let Some(arrow) = self.player.attack(target_x, target_y, &mut self.rng) {
+            self.arrows.push(arrow);
+            return;
+        }
+
+        let mut dead_indices = Vec::new();
+
+        // Cleaving weapons hit the whole arc. Otherwise only one monster is hit: the
+        // targeted one if it's in the arc, else the nearest (min_by keeps the lowest index on ties)
+        let in_arc: Vec<usize> = (0..self.monsters.len())
+            .filter(|&i| {
+                let monster = &self.monsters[i];
+                self.player.in_attack_cone(monster.x, monster.y, monster.monster_type.footprint_radius())
+            })
+            .collect();
+        let hits: Vec<usize> = if self.player.cleaves() {
+            in_arc
+        } else {
+            let targeted = self.targeted_monster.filter(|t| in_arc.contains(t));
+            let nearest = || {
+                in_arc.iter().copied().min_by(|&a, &b| {
+                    self.edge_distance_to_player(&self.monsters[a])
+                        .total_cmp(&self.edge_distance_to_player(&self.monsters[b]))
+                })
+            };
+            targeted.or_else(nearest).into_iter().collect()
+        };
+
+        let stun_chance = self.player.stun_chance();
+        for i in hits {
+            let damage = self.player.calculate_damage(&mut self.rng);
+            let stunned = stun_chance > 0.0 && self.rng.gen_f32() < stun_chance;
+            if self.settings.damage_numbers {
+                let (id, x, y) = (self.monsters[i].id, self.monsters[i].x, self.monsters[i].y);
+                self.push_damage_number(DamageTarget::Monster(id), damage, x, y);
+            }
+            let monster = &mut self.monsters[i];
+            monster.take_damage(damage);
+            if monster.health <= 0 {
+                dead_indices.push(i);
+            } else if stunned {
+                monster.stun();
+            }
+        }
 
-        // Monsters attacking player
+        // Remove dead monsters and spawn loot
+        for i in dead_indices.into_iter().rev() {
+            self.kill_monster(i);
+        }
+    }
+
+    fn handle_combat(&mut self) {
+        // Player attacking monsters (a shift-click is an attack-move order instead)
+        if is_mouse_button_pressed(MouseButton::Left) && !is_key_down(KeyCode::LeftShift) && self.player.can_attack() {
+            let (mouse_x, mouse_y) = mouse_position();
+            let (target_x, target_y) = self.camera.screen_to_world(mouse_x, mouse_y);
+            self.swing(target_x, target_y);
+        }
+
+        // Monsters can't touch the player inside town
+        if world::is_in_town(self.player.x, self.player.y) {
+            return;
+        }
+
+        // Monsters attacking player (reach is measured from the edge of their body)
+        let mut taken = Vec::new();
         for monster in &mut self.monsters {
             if monster.can_attack() {
                 let dx = self.player.x - monster.x;
                 let dy = self.player.y - monster.y;
-                let dist = (dx * dx + dy * dy).sqrt();
+                let dist = (dx * dx + dy * dy).sqrt() - monster.monster_type.footprint_radius();
 
                 if dist <= 1.0 {
                     monster.attack();
-                    let damage = monster.calculate_damage(&self.player);
-                    self.player.take_damage(damage);
+                    // Melee is softened by armor here and again in take_damage, as it always has been;
+                    // that double reduction is part of melee balance (breath and projectiles only get the one)
+                    let dtype = monster.monster_type.damage_type();
+                    let raw = (monster.calculate_damage(&mut self.rng) - self.player.damage_reduction_for(dtype)).max(1);
+                    let dealt = self.player.take_damage(raw, dtype, &mut self.rng);
+                    if dealt > 0 {
+                        taken.push(dealt);
+                    }
+
+                    // Venomous elites poison on a landed hit
+                    if dealt > 0
+                        && self.player.health > 0
+                        && let Some(effect) = monster.affix.and_then(|a| a.on_hit_effect())
+                    {
+                        self.player.apply_status(effect);
+                    }
+                }
+            }
+        }
+        if self.settings.damage_numbers {
+            for dealt in taken {
+                self.push_damage_number(DamageTarget::Player, dealt, self.player.x, self.player.y);
+            }
+        }
+    }
+
+    /// Move monster projectiles and resolve hits on the player
+    fn update_projectiles(&mut self, dt: f32) {
+        let player_in_town = world::is_in_town(self.player.x, self.player.y);
+        let mut hits = Vec::new();
+
+        self.projectiles.retain_mut(|projectile| {
+            if !projectile.update(dt, &self.world) {
+                return false;
+            }
+            if projectile.hits(self.player.x, self.player.y) {
+                // Bolts fizzle at the town boundary instead of hurting the player
+                if !player_in_town {
+                    hits.push((projectile.damage, projectile.damage_type));
                 }
+                return false;
+            }
+            true
+        });
+
+        for (damage, damage_type) in hits {
+            self.player.take_damage(damage, damage_type, &mut self.rng);
+        }
+    }
+
+    /// Fly thrown weapons; each one hits the first monster it touches and then lands where it stopped
+    fn update_thrown_weapons(&mut self, dt: f32) {
+        let mut i = 0;
+        while i < self.thrown_weapons.len() {
+            let thrown = &mut self.thrown_weapons[i];
+            let flying = thrown.update(dt, &self.world);
+            let hit = self
+                .monsters
+                .iter()
+                .position(|m| thrown.hits(m.x, m.y, m.monster_type.footprint_radius()));
+            let damage = thrown.damage;
+
+            if let Some(target) = hit {
+                self.hit_monster(target, damage);
+            }
+
+            if flying && hit.is_none() {
+                i += 1;
+                continue;
+            }
+            let thrown = self.thrown_weapons.swap_remove(i);
+            self.ground_items.push(GroundItem::thrown(thrown.x, thrown.y, thrown.weapon));
+        }
+    }
+
+    /// Fly arrows; each one is spent on the first monster it touches, or at the end of its range
+    fn update_arrows(&mut self, dt: f32) {
+        let mut i = 0;
+        while i < self.arrows.len() {
+            let arrow = &mut self.arrows[i];
+            let flying = arrow.update(dt, &self.world);
+            let hit = self
+                .monsters
+                .iter()
+                .position(|m| arrow.hits(m.x, m.y, m.monster_type.footprint_radius()));
+            let damage = arrow.damage;
+
+            if let Some(target) = hit {
+                self.hit_monster(target, damage);
+            }
+            if flying && hit.is_none() {
+                i += 1;
+            } else {
+                self.arrows.swap_remove(i);
+            }
+        }
+    }
+
+    /// Deal a projectile's damage to one monster, killing it if that finishes it off
+    fn hit_monster(&mut self, index: usize, damage: i32) {
+        let monster = &mut self.monsters[index];
+        monster.take_damage(damage);
+        let (id, x, y, dead) = (monster.id, monster.x, monster.y, monster.health <= 0);
+        if self.settings.damage_numbers {
+            self.push_damage_number(DamageTarget::Monster(id), damage, x, y);
+        }
+        if dead {
+            self.kill_monster(index);
+        }
+    }
+
+    /// Put every thrown weapon back on the player, e.g. before saving (ground items aren't saved).
+    /// It goes back in hand if that's empty, otherwise into the backpack if there's room.
+    fn recall_thrown_weapons(&mut self) {
+        let landed: Vec<usize> = (0..self.ground_items.len()).filter(|&i| self.ground_items[i].thrown).collect();
+        let mut weapons: Vec<Weapon> = self.thrown_weapons.drain(..).map(|t| t.weapon).collect();
+        for i in landed.into_iter().rev() {
+            match self.ground_items[i].item.clone() {
+                Item::Weapon(weapon) => weapons.push(weapon),
+                Item::Armor(_) => continue,
+            }
+            self.ground_items.remove(i);
+        }
+
+        for weapon in weapons {
+            if self.player.weapon.is_none() {
+                self.player.weapon = Some(weapon);
+            } else if !self.player.inventory.add_item(Item::Weapon(weapon.clone())) {
+                // Nowhere to put it: leave it at the player's feet rather than destroy it
+                self.ground_items.push(GroundItem::thrown(self.player.x, self.player.y, weapon));
+            }
+        }
+    }
+
+    /// Roll for new sandstorms over nearby desert, then drift them and pulse damage on
+    /// everything inside. Town is sheltered from storms.
+    fn update_hazards(&mut self, dt: f32) {
+        self.hazard_timer -= dt;
+        if self.hazard_timer <= 0.0 {
+            self.hazard_timer = Self::HAZARD_ROLL_INTERVAL;
+            if self.hazards.len() < Self::MAX_HAZARDS && rand::gen_range(0.0, 1.0) < Self::HAZARD_CHANCE {
+                let angle = rand::gen_range(0.0, std::f32::consts::TAU);
+                let distance = rand::gen_range(4.0, 9.0);
+                let x = self.player.x + angle.cos() * distance;
+                let y = self.player.y + angle.sin() * distance;
+                if self.world.get_terrain_at(x, y) == Terrain::Desert && !world::is_in_town(x, y) {
+                    self.hazards.push(Hazard::new_sandstorm(x, y));
+                }
+            }
+        }
+
+        self.hazards.retain_mut(|hazard| hazard.update(dt));
+
+        let mut player_damage = 0;
+        let mut dead = Vec::new();
+        for hazard in &mut self.hazards {
+            if !hazard.tick(dt) {
+                continue;
+            }
+            let damage = hazard.pulse_damage();
+            if hazard.contains(self.player.x, self.player.y) && !world::is_in_town(self.player.x, self.player.y) {
+                player_damage += damage;
+            }
+            for (i, monster) in self.monsters.iter_mut().enumerate() {
+                if monster.health > 0 && hazard.contains(monster.x, monster.y) && !world::is_in_town(monster.x, monster.y) {
+                    monster.take_damage(damage);
+                    if monster.health <= 0 {
+                        dead.push(i);
+                    }
+                }
+            }
+        }
+
+        if player_damage > 0 {
+            self.player.take_damage(player_damage, DamageType::Physical, &mut self.rng);
+        }
+        dead.sort_unstable();
+        for i in dead.into_iter().rev() {
+            self.kill_monster(i);
+        }
+    }
+
+    /// Walking over the corpse takes back its gold and as many items as fit
+    fn check_corpse_recovery(&mut self) {
+        let Some(corpse) = &mut self.corpse else {
+            return;
+        };
+        let dx = corpse.x - self.player.x;
+        let dy = corpse.y - self.player.y;
+        if dx * dx + dy * dy > 0.7 * 0.7 {
+            return;
+        }
+
+        let gold = std::mem::take(&mut corpse.gold);
+        self.player.gold += gold;
+        let mut recovered = 0;
+        while !corpse.items.is_empty() && !self.player.inventory.is_full() {
+            self.player.inventory.add_item(corpse.items.remove(0));
+            recovered += 1;
+        }
+
+        let text = if corpse.is_empty() {
+            self.corpse = None;
+            format!("Recovered {} gold and {} items", gold, recovered)
+        } else if gold > 0 || recovered > 0 {
+            "Backpack full - some items remain on your corpse".to_string()
+        } else {
+            return;
+        };
+        self.push_floating_text(text, self.player.x, self.player.y);
+    }
+
+    fn item_distance(&self, ground_item: &GroundItem) -> f32 {
+        let dx = ground_item.x - self.player.x;
+        let dy = ground_item.y - self.player.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    /// Whether G would pick this item up right now (only used with auto-pickup off)
+    fn in_manual_pickup_range(&self, ground_item: &GroundItem) -> bool {
+        let reach = Self::MANUAL_PICKUP_RANGE.max(self.player.pickup_range());
+        !self.settings.auto_pickup && self.item_distance(ground_item) <= reach
+    }
+
+    /// Magnetism draws in loot the player has room for: backpack items while it has a free slot,
+    /// a thrown weapon while the hand is empty
+    fn pull_ground_items(&mut self, dt: f32) {
+        if self.player.magnetism == 0 {
+            return;
+        }
+        let range = self.player.magnet_range();
+        let backpack_room = !self.player.inventory.is_full();
+        let hand_free = self.player.weapon.is_none();
+        let (player_x, player_y) = (self.player.x, self.player.y);
+        for ground_item in &mut self.ground_items {
+            let (dx, dy) = (ground_item.x - player_x, ground_item.y - player_y);
+            let has_room = if ground_item.thrown { hand_free } else { backpack_room };
+            if has_room && !ground_item.dropped && dx * dx + dy * dy <= range * range {
+                ground_item.pull_toward(player_x, player_y, dt);
             }
         }
     }
 
     fn check_item_pickup(&mut self) {
-        let pickup_range = 0.5;
+        let pickup_range = self.player.pickup_range();
+        // Walking over a thrown weapon always takes it back into an empty hand
+        if self.player.weapon.is_none()
+            && let Some(i) = self
+                .ground_items
+                .iter()
+                .position(|g| g.thrown && self.item_distance(g) <= pickup_range)
+            && let Item::Weapon(weapon) = self.ground_items.remove(i).item
+        {
+            self.push_floating_text(format!("Caught your {}", weapon.name()), self.player.x, self.player.y);
+            self.player.weapon = Some(weapon);
+        }
+
+        if !self.settings.auto_pickup {
+            if is_key_pressed(KeyCode::G) {
+                self.pick_up_nearest();
+            }
+            return;
+        }
+        if self.player.inventory.is_full() {
+            return;
+        }
+
         let mut picked_items: Vec<(usize, String)> = Vec::new();
 
         for (i, ground_item) in self.ground_items.iter().enumerate() {
-            let dx = ground_item.x - self.player.x;
-            let dy = ground_item.y - self.player.y;
-            let dist = (dx * dx + dy * dy).sqrt();
-
-            if dist <= pickup_range {
-                if self.player.inventory.add_item(ground_item.item.clone()) {
-                    let item_name = ground_item.item.name().to_string();
-                    picked_items.push((i, item_name));
-                }
+            if !ground_item.dropped
+                && self.item_distance(ground_item) <= pickup_range
+                && self.player.inventory.add_item(ground_item.item.clone())
+            {
+                let item_name = ground_item.item.name().to_string();
+                picked_items.push((i, item_name));
             }
         }
 
@@ -300,56 +1569,248 @@ impl Game {
             self.ground_items.remove(i);
             // Spawn floating text
             let text = format!("Picked up {}!", item_name);
-            self.floating_texts.push(FloatingText::new(
-                text,
-                self.player.x,
-                self.player.y,
-            ));
+            self.push_floating_text(text, self.player.x, self.player.y);
         }
     }
 
+    /// Manual pickup: take the closest item in reach, one per key press
+    fn pick_up_nearest(&mut self) {
+        let nearest = (0..self.ground_items.len())
+            .filter(|&i| self.in_manual_pickup_range(&self.ground_items[i]))
+            .min_by(|&a, &b| {
+                self.item_distance(&self.ground_items[a])
+                    .total_cmp(&self.item_distance(&self.ground_items[b]))
+            });
+        let Some(i) = nearest else {
+            return;
+        };
+
+        let text = if self.player.inventory.add_item(self.ground_items[i].item.clone()) {
+            format!("Picked up {}!", self.ground_items.remove(i).item.name())
+        } else {
+            "Backpack full!".to_string()
+        };
+        self.push_floating_text(text, self.player.x, self.player.y);
+    }
+
     pub fn draw(&self) {
         clear_background(Color::from_rgba(30, 30, 40, 255));
 
         match self.state {
-            GameState::Playing => self.draw_playing(),
+            GameState::MainMenu => {
+                ui::draw_main_menu(self.menu_selection, &self.slot_infos, &self.seed_input, self.difficulty, self.class);
+                return; // No HUD on the title screen
+            }
+            GameState::Playing => {
+                self.draw_playing();
+                ui::draw_low_health_vignette(self.player.health_fraction(), get_time() as f32, self.settings.palette());
+            }
             GameState::Inventory => {
                 self.draw_playing(); // Draw game behind
                 self.draw_inventory();
             }
+            GameState::Paused => {
+                self.draw_playing(); // Draw frozen game behind
+                self.draw_paused();
+            }
+            GameState::LevelUp => {
+                self.draw_playing(); // Draw frozen game behind
+                ui::draw_level_up(&self.player);
+            }
+            GameState::Shop => {
+                self.draw_playing(); // Draw game behind
+                vendor::draw_shop_screen(&self.vendor, &self.player, self.settings.palette(), self.settings.ui_scale);
+            }
             GameState::GameOver => self.draw_game_over(),
+            GameState::Settings => {
+                if matches!(self.settings_return, GameState::MainMenu) {
+                    ui::draw_settings(&self.settings, self.settings_selection);
+                    return; // No HUD on the title screen
+                }
+                self.draw_playing(); // Draw frozen game behind
+                ui::draw_settings(&self.settings, self.settings_selection);
+            }
         }
 
         // Always draw UI
-        ui::draw_health_bar(self.player.health, self.player.max_health);
+        let scale = self.settings.ui_scale;
+        ui::draw_health_bar(self.player.health, self.player.max_health(), &self.hud, self.settings.palette(), scale);
+        ui::draw_xp_bar(self.player.level, &self.hud, scale);
+        ui::draw_gold(self.player.gold, scale);
+        ui::draw_difficulty(self.difficulty, scale);
+        ui::draw_quest(&self.quest, scale);
+        ui::draw_playtime(self.play_time, scale);
+        if self.camera.free_look {
+            ui::draw_free_look_hint();
+        }
+        ui::draw_combo(self.player.combo, self.player.combo_multiplier(), self.player.combo_timer, scale);
+        if self.player.is_poisoned() {
+            ui::draw_poison_indicator(scale);
+        }
+        if self.show_seed {
+            ui::draw_seed(self.seed);
+        }
+        ui::draw_minimap(&self.minimap, self.player.x, self.player.y);
+
+        if self.debug {
+            ui::draw_debug(
+                self.monsters.len(),
+                self.ground_items.len(),
+                self.spawned_chunks.len(),
+                self.player.x,
+                self.player.y,
+            );
+        }
     }
 
     fn draw_playing(&self) {
         // Draw world
-        self.world.draw(&self.camera);
+        self.world.draw(&self.camera, self.player.x, self.player.y);
+        if self.debug {
+            self.world.draw_debug_grid(&self.camera, self.player.x, self.player.y);
+        }
+        world::draw_hover_tile(&self.camera);
+        if let Some((x, y)) = self.attack_move {
+            let (screen_x, screen_y) = self.camera.world_to_screen(x, y);
+            draw_ellipse_lines(screen_x, screen_y, 10.0, 5.0, 0.0, 2.0, Color::from_rgba(255, 90, 60, 200));
+        }
+        self.footprints.draw(&self.camera);
+        // Holding attack (or Alt) shows how far a swing reaches
+        if is_mouse_button_down(MouseButton::Left) || is_key_down(KeyCode::LeftAlt) {
+            self.player.draw_attack_range(&self.camera);
+        }
+        for hazard in &self.hazards {
+            hazard.draw(&self.camera);
+        }
+        let light_at = |x: f32, y: f32| world::vision_light(x - self.player.x, y - self.player.y);
+
+        // Queue decorations, ground items, monsters and the player, then draw back to front
+        let decorations = self.world.visible_decorations(&self.camera);
+        let mut commands: Vec<DrawCommand> =
+            Vec::with_capacity(decorations.len() + self.ground_items.len() + self.monsters.len() + 3);
+        commands.extend(decorations.into_iter().map(|(x, y, d)| DrawCommand::Decoration(x, y, d)));
+        commands.extend(self.ground_items.iter().map(DrawCommand::GroundItem));
+        commands.extend(self.monsters.iter().map(DrawCommand::Monster));
+        commands.push(DrawCommand::Vendor(&self.vendor));
+        commands.extend(self.corpse.as_ref().map(DrawCommand::Corpse));
+        commands.push(DrawCommand::Player);
+
+        // Stable sort keeps spawn order for entities at equal depth
+        commands.sort_by(|a, b| a.depth(&self.player).total_cmp(&b.depth(&self.player)));
+
+        for command in &commands {
+            match command {
+                DrawCommand::Decoration(x, y, decoration) => {
+                    let (screen_x, screen_y) = self.camera.world_to_screen(*x, *y);
+                    decoration.draw(screen_x, screen_y, light_at(*x, *y));
+                }
+                DrawCommand::GroundItem(item) => {
+                    let (screen_x, screen_y) = self.camera.world_to_screen(item.x, item.y);
+                    inventory::draw_ground_item(item, screen_x, screen_y, self.in_manual_pickup_range(item), self.settings.palette());
+                }
+                DrawCommand::Monster(monster) => monster.draw(&self.camera, light_at(monster.x, monster.y), self.settings.palette()),
+                DrawCommand::Vendor(vendor) => vendor.draw(&self.camera),
+                DrawCommand::Corpse(corpse) => {
+                    let (screen_x, screen_y) = self.camera.world_to_screen(corpse.x, corpse.y);
+                    corpse.draw(screen_x, screen_y);
+                }
+                DrawCommand::Player => self.player.draw(&self.camera),
+            }
+        }
+
+        // Projectiles fly above everything on the ground
+        for projectile in &self.projectiles {
+            projectile.draw(&self.camera);
+        }
+        for thrown in &self.thrown_weapons {
+            thrown.draw(&self.camera);
+        }
+        for arrow in &self.arrows {
+            arrow.draw(&self.camera);
+        }
+        for particle in &self.particles {
+            particle.draw(&self.camera);
+        }
 
-        // Draw ground items
-        for item in &self.ground_items {
-            let (screen_x, screen_y) = self.camera.world_to_screen(item.x, item.y);
-            inventory::draw_ground_item(item, screen_x, screen_y);
+        // Biome ambience over the scene, under markers and text
+        if self.ambient_tint.a > 0.001 {
+            draw_rectangle(0.0, 0.0, screen_width(), screen_height(), self.ambient_tint);
         }
+        self.weather.draw();
 
-        // Draw monsters
-        for monster in &self.monsters {
-            monster.draw(&self.camera);
+        // Selection reticle around the targeted monster
+        if let Some(monster) = self.targeted_monster.and_then(|t| self.monsters.get(t)) {
+            let (screen_x, screen_y) = self.camera.world_to_screen(monster.x, monster.y);
+            let size = monster.monster_type.size();
+            ui::draw_target_reticle(screen_x, screen_y - monster.body_lift() - size * 0.5, size + 10.0);
         }
 
-        // Draw player
-        self.player.draw(&self.camera);
+        // Waypoint marker, or an edge arrow when it's off-screen
+        if let Some((waypoint_x, waypoint_y)) = self.waypoint {
+            ui::draw_waypoint(&self.camera, waypoint_x, waypoint_y);
+        }
 
         // Draw floating texts
         for text in &self.floating_texts {
-            text.draw(&self.camera);
+            text.draw(&self.camera, self.settings.ui_scale);
+        }
+
+        // Swing readiness follows the mouse, but only while actually playing
+        if matches!(self.state, GameState::Playing) {
+            ui::draw_cooldown_cursor(self.player.attack_readiness());
         }
     }
 
     fn draw_inventory(&self) {
-        inventory::draw_inventory_screen(&self.player);
+        inventory::draw_inventory_screen(
+            &self.player,
+            self.inventory_page,
+            self.dragging,
+            self.selected_slot,
+            self.context_menu.as_ref(),
+            self.settings.palette(),
+            self.settings.ui_scale,
+        );
+    }
+
+    fn draw_paused(&self) {
+        let screen_w = screen_width();
+        let screen_h = screen_height();
+
+        draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::from_rgba(0, 0, 0, 150));
+
+        let text = "PAUSED";
+        let font_size = 64.0;
+        let text_dims = measure_text(text, None, font_size as u16, 1.0);
+        draw_text(
+            text,
+            screen_w / 2.0 - text_dims.width / 2.0,
+            screen_h / 2.0,
+            font_size,
+            WHITE,
+        );
+
+        let resume_text = "Press P to resume | F5 to save | O for settings | Shift-click to attack-move | Q to quit to menu";
+        let pickup_text = format!(
+            "G: auto-pickup {}",
+            if self.settings.auto_pickup { "ON" } else { "OFF (press G to pick up)" }
+        );
+        let pickup_dims = measure_text(&pickup_text, None, 20, 1.0);
+        draw_text(
+            &pickup_text,
+            screen_w / 2.0 - pickup_dims.width / 2.0,
+            screen_h / 2.0 + 85.0,
+            20.0,
+            GRAY,
+        );
+        let resume_dims = measure_text(resume_text, None, 24, 1.0);
+        draw_text(
+            resume_text,
+            screen_w / 2.0 - resume_dims.width / 2.0,
+            screen_h / 2.0 + 50.0,
+            24.0,
+            LIGHTGRAY,
+        );
     }
 
     fn draw_game_over(&self) {
@@ -358,7 +1819,7 @@ impl Game {
 
         draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::from_rgba(0, 0, 0, 200));
 
-        let text = "GAME OVER";
+        let text = "YOU DIED";
         let font_size = 64.0;
         let text_dims = measure_text(text, None, font_size as u16, 1.0);
         draw_text(
@@ -369,7 +1830,13 @@ impl Game {
             RED,
         );
 
-        let restart_text = "Press SPACE or ENTER to restart";
+        let restart_text = if self.difficulty.is_permadeath() {
+            "Death is permanent on Hell | Press ENTER or Q to return to the menu"
+        } else if self.waypoint.is_some() {
+            "Press SPACE or ENTER to respawn at your waypoint | Q to quit to menu"
+        } else {
+            "Press SPACE or ENTER to respawn in town | Q to quit to menu"
+        };
         let restart_dims = measure_text(restart_text, None, 24, 1.0);
         draw_text(
             restart_text,
@@ -378,6 +1845,32 @@ impl Game {
             24.0,
             WHITE,
         );
+
+        let time_text = format!("Time played: {}", ui::format_playtime(self.play_time));
+        let time_dims = measure_text(&time_text, None, 20, 1.0);
+        draw_text(&time_text, screen_w / 2.0 - time_dims.width / 2.0, screen_h / 2.0 + 115.0, 20.0, GRAY);
+
+        if let Some(corpse) = &self.corpse {
+            let corpse_text = format!(
+                "Your corpse holds {} gold and {} items - walk back to recover them",
+                corpse.gold,
+                corpse.items.len()
+            );
+            let corpse_dims = measure_text(&corpse_text, None, 20, 1.0);
+            draw_text(
+                &corpse_text,
+                screen_w / 2.0 - corpse_dims.width / 2.0,
+                screen_h / 2.0 + 85.0,
+                20.0,
+                LIGHTGRAY,
+            );
+        }
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -386,10 +1879,42 @@ fn window_conf() -> Conf {
         window_title: "Diablo Clone".to_owned(),
         window_width: 1280,
         window_height: 720,
+        window_resizable: true, // All layout is derived from the current screen size each frame
         ..Default::default()
     }
 }
 
+/// Seed passed as `--seed N` or `--seed=N`; validated when the run starts
+fn cli_seed() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--seed" {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix("--seed=") {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+/// Append to an oldest-first queue, evicting from the front so it never holds more than `cap`
+fn push_bounded<T>(queue: &mut VecDeque<T>, item: T, cap: usize) {
+    while !queue.is_empty() && queue.len() >= cap {
+        queue.pop_front();
+    }
+    queue.push_back(item);
+}
+
+/// Clock-derived seed for runs started without a valid one
+fn random_seed() -> u32 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    GameRng::new(nanos).next_u64() as u32
+}
+
 #[macroquad::main(window_conf)]
 async fn main() {
     let mut game = Game::new();
@@ -401,3 +1926,22 @@ async fn main() {
         next_frame().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn floating_texts_stay_bounded() {
+        let mut texts = VecDeque::new();
+        for i in 0..200 {
+            push_bounded(&mut texts, FloatingText::new(format!("text {}", i), 0.0, 0.0), Game::MAX_FLOATING_TEXTS);
+            assert!(texts.len() <= Game::MAX_FLOATING_TEXTS);
+        }
+        assert_eq!(texts.len(), Game::MAX_FLOATING_TEXTS);
+        // The oldest were dropped; the newest survive in order
+        let first_kept = 200 - Game::MAX_FLOATING_TEXTS;
+        assert_eq!(texts.front().map(|t| t.text.as_str()), Some(format!("text {}", first_kept).as_str()));
+        assert_eq!(texts.back().map(|t| t.text.as_str()), Some("text 199"));
+    }
+}
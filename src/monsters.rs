@@ -1,9 +1,13 @@
 use macroquad::prelude::*;
 
+use crate::balance::{self, MonsterStats};
 use crate::camera::GameCamera;
-use crate::combat::{calculate_damage, Item};
-use crate::player::Player;
-use crate::world::Terrain;
+use crate::combat::{Armor, ArmorType, DamageType, Item, Weapon, WeaponType, UNIQUES};
+use crate::difficulty::{self, Difficulty};
+use crate::player::StatusEffect;
+use crate::rng::GameRng;
+use crate::ui::Palette;
+use crate::world::{draw_shadow, shade, Terrain, World};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum MonsterType {
@@ -16,26 +20,62 @@ pub enum MonsterType {
 }
 
 impl MonsterType {
-    pub fn max_health(&self) -> i32 {
+    pub const ALL: [MonsterType; 6] = [
+        MonsterType::Goblin,
+        MonsterType::Ogre,
+        MonsterType::Orc,
+        MonsterType::Wyrm,
+        MonsterType::SnowGoblin,
+        MonsterType::Yeti,
+    ];
+
+    pub fn name(&self) -> &'static str {
         match self {
-            MonsterType::Goblin => 10,
-            MonsterType::Ogre => 30,
-            MonsterType::Orc => 20,
-            MonsterType::Wyrm => 50,
-            MonsterType::SnowGoblin => 10,
-            MonsterType::Yeti => 30,
+            MonsterType::Goblin => "Goblin",
+            MonsterType::Ogre => "Ogre",
+            MonsterType::Orc => "Orc",
+            MonsterType::Wyrm => "Wyrm",
+            MonsterType::SnowGoblin => "Snow Goblin",
+            MonsterType::Yeti => "Yeti",
         }
     }
 
+    pub fn from_name(name: &str) -> Option<MonsterType> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+
+    /// Built-in balance numbers; `balance.cfg` can override any of them.
+    /// Small monsters jab quickly, big ones wind up; hits roll base damage plus or minus the spread.
+    pub fn default_stats(&self) -> MonsterStats {
+        // (max health, base damage, damage spread, attack cooldown, drop chance).
+        // Damage over cooldown matches the old flat 0.5s swing: quick types hit softer, slow ones harder.
+        let (max_health, base_damage, damage_spread, attack_cooldown, drop_chance) = match self {
+            MonsterType::Goblin => (10, 4, 1, 0.4, 0.2),
+            MonsterType::Ogre => (30, 10, 3, 0.625, 0.3),
+            MonsterType::Orc => (20, 6, 2, 0.5, 0.25),
+            MonsterType::Wyrm => (50, 12, 2, 0.6, 0.45),
+            MonsterType::SnowGoblin => (10, 5, 1, 0.5, 0.2),
+            MonsterType::Yeti => (30, 12, 3, 0.75, 0.3),
+        };
+        MonsterStats { max_health, base_damage, damage_spread, attack_cooldown, drop_chance }
+    }
+
+    pub fn max_health(&self) -> i32 {
+        balance::monster(*self).max_health
+    }
+
     pub fn base_damage(&self) -> i32 {
-        match self {
-            MonsterType::Goblin => 5,
-            MonsterType::Ogre => 8,
-            MonsterType::Orc => 6,
-            MonsterType::Wyrm => 10,
-            MonsterType::SnowGoblin => 5,
-            MonsterType::Yeti => 8,
-        }
+        balance::monster(*self).base_damage
+    }
+
+    /// Hits roll `base_damage` plus or minus this, so the average is unchanged
+    pub fn damage_spread(&self) -> i32 {
+        balance::monster(*self).damage_spread
+    }
+
+    /// Seconds between melee hits
+    pub fn attack_cooldown(&self) -> f32 {
+        balance::monster(*self).attack_cooldown
     }
 
     pub fn color(&self) -> Color {
@@ -49,6 +89,45 @@ impl MonsterType {
         }
     }
 
+    /// What this type's melee hits are made of
+    pub fn damage_type(&self) -> DamageType {
+        match self {
+            MonsterType::Wyrm => DamageType::Fire,
+            _ => DamageType::Physical,
+        }
+    }
+
+    /// Cowards that run from the player once badly hurt
+    pub fn flees_when_low(&self) -> bool {
+        matches!(self, MonsterType::Goblin | MonsterType::SnowGoblin)
+    }
+
+    /// Flyers pass over solid decorations and never need to path around them
+    pub fn is_flying(&self) -> bool {
+        matches!(self, MonsterType::Wyrm)
+    }
+
+    /// Radius of the body in tiles. Reach and hit checks measure to this edge rather than
+    /// the center, so big monsters can be hit (and hit back) from a step further out.
+    pub fn footprint_radius(&self) -> f32 {
+        match self {
+            MonsterType::Goblin | MonsterType::SnowGoblin => 0.0,
+            MonsterType::Orc => 0.2,
+            MonsterType::Wyrm => 0.5,
+            MonsterType::Ogre | MonsterType::Yeti => 0.6,
+        }
+    }
+
+    /// How close the player must come before this type notices and gives chase
+    pub fn detection_range(&self) -> f32 {
+        match self {
+            MonsterType::Goblin | MonsterType::SnowGoblin => 8.0,
+            MonsterType::Ogre => 9.0,
+            MonsterType::Orc | MonsterType::Yeti => 10.0,
+            MonsterType::Wyrm => 13.0, // Circles high and spots you from afar
+        }
+    }
+
     pub fn size(&self) -> f32 {
         match self {
             MonsterType::Goblin | MonsterType::SnowGoblin => 12.0,
@@ -57,6 +136,65 @@ impl MonsterType {
         }
     }
 
+    /// Weighted item pool, with the drop chance from the balance table
+    pub fn loot_table(&self) -> LootTable {
+        use LootEntry::{Armor as A, Weapon as W};
+        let drop_chance = balance::monster(*self).drop_chance;
+        match self {
+            MonsterType::Goblin | MonsterType::SnowGoblin => LootTable {
+                drop_chance,
+                entries: &[
+                    (W(WeaponType::Sword), 4),
+                    (W(WeaponType::Axe), 1),
+                    (W(WeaponType::Bow), 2),
+                    (A(ArmorType::Leather), 4),
+                    (A(ArmorType::Shield), 2),
+                ],
+            },
+            MonsterType::Orc => LootTable {
+                drop_chance,
+                entries: &[
+                    (W(WeaponType::Sword), 2),
+                    (W(WeaponType::Axe), 3),
+                    (W(WeaponType::Bow), 2),
+                    (A(ArmorType::Leather), 2),
+                    (A(ArmorType::Chainmail), 3),
+                    (A(ArmorType::Shield), 2),
+                ],
+            },
+            MonsterType::Ogre | MonsterType::Yeti => LootTable {
+                drop_chance,
+                entries: &[
+                    (W(WeaponType::Axe), 2),
+                    (W(WeaponType::Mace), 3),
+                    (A(ArmorType::Chainmail), 3),
+                    (A(ArmorType::Platemail), 1),
+                    (A(ArmorType::Shield), 2),
+                ],
+            },
+            MonsterType::Wyrm => LootTable {
+                drop_chance,
+                entries: &[
+                    (W(WeaponType::Axe), 2),
+                    (W(WeaponType::Mace), 2),
+                    (A(ArmorType::Chainmail), 2),
+                    (A(ArmorType::Platemail), 4),
+                    (A(ArmorType::Shield), 1),
+                ],
+            },
+        }
+    }
+
+    /// Whether elites of this type can drop uniques
+    pub fn drops_uniques(&self) -> bool {
+        matches!(self, MonsterType::Ogre | MonsterType::Wyrm | MonsterType::Yeti)
+    }
+
+    /// Whether this monster keeps its distance with a ranged attack
+    pub fn is_ranged(&self) -> bool {
+        matches!(self, MonsterType::Wyrm)
+    }
+
     pub fn for_terrain(terrain: Terrain) -> Vec<MonsterType> {
         match terrain {
             Terrain::Grass => vec![MonsterType::Goblin, MonsterType::Ogre],
@@ -65,88 +203,662 @@ impl MonsterType {
         }
     }
 
-    pub fn random_for_terrain(terrain: Terrain) -> MonsterType {
+    /// Pick a type for `terrain` from a caller-supplied roll so chunk spawns stay seed-deterministic
+    pub fn pick_for_terrain(terrain: Terrain, roll: u32) -> MonsterType {
         let types = Self::for_terrain(terrain);
-        let idx = rand::gen_range(0, types.len());
-        types[idx]
+        types[roll as usize % types.len()]
+    }
+}
+
+/// One possible drop in a loot table
+#[derive(Clone, Copy, Debug)]
+pub enum LootEntry {
+    Weapon(WeaponType),
+    Armor(ArmorType),
+}
+
+impl LootEntry {
+    pub fn to_item(self) -> Item {
+        match self {
+            LootEntry::Weapon(kind) => Item::Weapon(Weapon::new(kind)),
+            LootEntry::Armor(kind) => Item::Armor(Armor::new(kind)),
+        }
+    }
+}
+
+pub struct LootTable {
+    pub drop_chance: f32,
+    pub entries: &'static [(LootEntry, u32)], // (drop, relative weight)
+}
+
+impl LootTable {
+    /// Pick an entry in proportion to its weight
+    pub fn pick(&self, rng: &mut GameRng) -> Option<Item> {
+        let total: u32 = self.entries.iter().map(|&(_, weight)| weight).sum();
+        let mut roll = rng.gen_range(0, total as i32) as u32;
+        for &(entry, weight) in self.entries {
+            if roll < weight {
+                return Some(entry.to_item());
+            }
+            roll -= weight;
+        }
+        None
+    }
+}
+
+/// Elite modifier rolled on some spawns
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Affix {
+    Fast,     // Moves faster
+    Tough,    // Double health
+    Venomous, // Hits poison the player
+}
+
+impl Affix {
+    pub fn from_index(index: u32) -> Affix {
+        match index % 3 {
+            0 => Affix::Fast,
+            1 => Affix::Tough,
+            _ => Affix::Venomous,
+        }
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        match self {
+            Affix::Fast => 1.6,
+            _ => 1.0,
+        }
+    }
+
+    pub fn health_multiplier(&self) -> i32 {
+        match self {
+            Affix::Tough => 2,
+            _ => 1,
+        }
+    }
+
+    /// Status effect this affix applies when the monster's hit lands
+    pub fn on_hit_effect(&self) -> Option<StatusEffect> {
+        match self {
+            Affix::Venomous => Some(StatusEffect::Poison { dps: 2.0, remaining: 4.0 }),
+            _ => None,
+        }
+    }
+
+    /// Aura color - saturated hues picked to stand apart from every base monster color
+    pub fn aura_color(&self) -> Color {
+        match self {
+            Affix::Fast => Color::from_rgba(255, 215, 0, 255),     // Gold
+            Affix::Tough => Color::from_rgba(220, 40, 40, 255),    // Blood red
+            Affix::Venomous => Color::from_rgba(180, 60, 255, 255), // Toxic purple
+        }
+    }
+}
+
+/// Fire bolt flying in a straight line; it only hurts if it reaches the player
+pub struct Projectile {
+    pub x: f32,
+    pub y: f32,
+    pub vx: f32, // Tiles per second
+    pub vy: f32,
+    pub damage: i32, // Raw damage - armor is applied by `Player::take_damage`
+    pub damage_type: DamageType,
+    pub lifetime: f32,
+}
+
+impl Projectile {
+    const HIT_RADIUS: f32 = 0.4;
+
+    /// Advance the bolt; returns false once it has burnt out or hit a solid decoration
+    pub fn update(&mut self, dt: f32, world: &World) -> bool {
+        self.x += self.vx * dt;
+        self.y += self.vy * dt;
+        self.lifetime -= dt;
+        self.lifetime > 0.0 && !world.is_blocked(self.x, self.y)
+    }
+
+    pub fn hits(&self, x: f32, y: f32) -> bool {
+        let dx = x - self.x;
+        let dy = y - self.y;
+        dx * dx + dy * dy <= Self::HIT_RADIUS * Self::HIT_RADIUS
+    }
+
+    pub fn draw(&self, camera: &GameCamera) {
+        let (screen_x, screen_y) = camera.world_to_screen(self.x, self.y);
+        // Ground shadow so the bolt's position is readable, then the flame at chest height
+        draw_shadow(screen_x, screen_y, 6.0);
+        draw_circle(screen_x, screen_y - 18.0, 8.0, Color::from_rgba(255, 120, 20, 160));
+        draw_circle(screen_x, screen_y - 18.0, 4.5, Color::from_rgba(255, 230, 120, 255));
+    }
+}
+
+/// Snapshot of a monster whose chunk was unloaded, so it comes back as it was left
+#[derive(Clone, Debug)]
+pub struct MonsterState {
+    pub x: f32,
+    pub y: f32,
+    pub home_x: f32,
+    pub home_y: f32,
+    pub health: i32,
+    pub monster_type: MonsterType,
+    pub affix: Option<Affix>,
+    pub pack: Option<u32>,
+}
+
+/// What a pack member's packmates are doing, gathered before monsters update
+#[derive(Clone, Copy, Debug)]
+pub struct Flock {
+    center_x: f32, // Average packmate position
+    center_y: f32,
+    heading_x: f32, // Average packmate velocity, in tiles per second
+    heading_y: f32,
+}
+
+impl Flock {
+    pub const RADIUS: f32 = 6.0; // Packmates further apart than this ignore each other
+    pub const COHESION: f32 = 0.4; // Pull toward the pack's center, against 1.0 for the player
+    pub const ALIGNMENT: f32 = 0.3; // Pull toward the pack's heading
+    pub const ENGAGE_DISTANCE: f32 = 3.0; // Closer to the player than this, the pull fades out
+
+    /// Packmates of `monsters[index]` in range: same pack and same type. None for a lone monster.
+    pub fn around(monsters: &[Monster], index: usize) -> Option<Self> {
+        let monster = &monsters[index];
+        let pack = monster.pack?;
+        let (mut sum_x, mut sum_y, mut sum_vx, mut sum_vy, mut count) = (0.0, 0.0, 0.0, 0.0, 0);
+        for (i, other) in monsters.iter().enumerate() {
+            let (dx, dy) = (other.x - monster.x, other.y - monster.y);
+            if i == index
+                || other.pack != Some(pack)
+                || other.monster_type != monster.monster_type
+                || dx * dx + dy * dy > Self::RADIUS * Self::RADIUS
+            {
+                continue;
+            }
+            sum_x += other.x;
+            sum_y += other.y;
+            sum_vx += other.vx;
+            sum_vy += other.vy;
+            count += 1;
+        }
+        (count > 0).then(|| {
+            let n = count as f32;
+            Self { center_x: sum_x / n, center_y: sum_y / n, heading_x: sum_vx / n, heading_y: sum_vy / n }
+        })
     }
 }
 
 pub struct Monster {
+    pub id: u32, // Unique among active monsters; handed out by the game as the monster joins
     pub x: f32,
     pub y: f32,
     pub health: i32,
     pub max_health: i32,
     pub monster_type: MonsterType,
+    pub damage: i32, // Base hit damage, scaled by difficulty
+    pub difficulty: Difficulty,
     pub attack_cooldown: f32,
     pub speed: f32,
+    pub affix: Option<Affix>,
+    pub home_x: f32, // Spawn point the monster is leashed to
+    pub home_y: f32,
+    pub leash_distance: f32, // Max distance from home before giving up a chase
+    pub returning: bool,
+    pub ranged_cooldown: f32, // Time until the next ranged attack (ranged types only)
+    pub wander_target: Option<(f32, f32)>, // Idle stroll destination near home
+    pub pack: Option<u32>, // Shared by a pack's members, who chase as a loose group
+    vx: f32, // Last frame's velocity, in tiles per second
+    vy: f32,
+    wander_timer: f32,                     // Time until a new stroll destination is picked
+    hit_flash_timer: f32,                  // Counts down after taking damage; body drawn whitened
+    stun_timer: f32,                       // Stunned monsters stand still and can't attack
+    stun_immunity: f32,                    // Covers the stun plus a grace period so stuns can't chain
+    path: Vec<(i32, i32)>, // Detour tiles around obstacles, next waypoint last
+    repath_timer: f32,
+    regen_timer: f32,
+    sight_memory: f32, // Keeps chasing this long after the player slips out of sight
+    fleeing: bool,     // Ran from the player this frame, so it isn't attacking
 }
 
 impl Monster {
-    pub fn new(x: f32, y: f32, monster_type: MonsterType) -> Self {
-        let max_health = monster_type.max_health();
+    pub const DEFAULT_LEASH_DISTANCE: f32 = 15.0;
+    const UNIQUE_CHANCE: f32 = 0.04; // Per kill, for an eligible elite
+    const FLIGHT_HEIGHT: f32 = 14.0; // Pixels flyers hover above their shadow
+    const LEASH_PAST_DETECTION: f32 = 5.0; // Leash always reaches this far beyond detection range
+    const SIGHT_MEMORY: f32 = 1.5;
+    const FLEE_HEALTH: f32 = 0.3; // Cowards run below this fraction of max health
+    const WANDER_RADIUS: f32 = 3.0; // Idle strolls stay this close to home
+    const WANDER_SPEED_FACTOR: f32 = 0.35;
+    const BREATH_RANGE: f32 = 5.0;
+    const BREATH_COOLDOWN: f32 = 2.5;
+    const BREATH_SPEED: f32 = 6.0; // Tiles per second - slow enough to sidestep or dash through
+    const HIT_FLASH_DURATION: f32 = 0.1;
+    const STUN_DURATION: f32 = 0.8;
+    const STUN_IMMUNITY: f32 = 1.5; // Extra time after a stun wears off before another can land
+
+    pub fn new(x: f32, y: f32, monster_type: MonsterType, difficulty: Difficulty) -> Self {
+        let max_health = difficulty::scale(monster_type.max_health(), difficulty.health_multiplier()).max(1);
         Self {
+            id: 0,
             x,
             y,
             health: max_health,
             max_health,
             monster_type,
+            damage: difficulty::scale(monster_type.base_damage(), difficulty.damage_multiplier()),
+            difficulty,
             attack_cooldown: 0.0,
             speed: 4.0, // Slightly slower than player (5 tiles/sec)
+            affix: None,
+            home_x: x,
+            home_y: y,
+            // Keep the leash longer than detection, or a monster could spot the player
+            // from a spot it's already too far from home to chase from
+            leash_distance: Self::DEFAULT_LEASH_DISTANCE.max(monster_type.detection_range() + Self::LEASH_PAST_DETECTION),
+            returning: false,
+            ranged_cooldown: 0.0,
+            wander_target: None,
+            pack: None,
+            vx: 0.0,
+            vy: 0.0,
+            wander_timer: rand::gen_range(0.0, 3.0), // Stagger so spawns don't all set off at once
+            hit_flash_timer: 0.0,
+            stun_timer: 0.0,
+            stun_immunity: 0.0,
+            path: Vec::new(),
+            repath_timer: 0.0,
+            regen_timer: 0.0,
+            sight_memory: 0.0,
+            fleeing: false,
+        }
+    }
+
+    pub fn new_elite(x: f32, y: f32, monster_type: MonsterType, affix: Affix, difficulty: Difficulty) -> Self {
+        let mut monster = Self::new(x, y, monster_type, difficulty);
+        monster.max_health *= affix.health_multiplier();
+        monster.health = monster.max_health;
+        monster.speed *= affix.speed_multiplier();
+        monster.affix = Some(affix);
+        monster
+    }
+
+    pub fn to_state(&self) -> MonsterState {
+        MonsterState {
+            x: self.x,
+            y: self.y,
+            home_x: self.home_x,
+            home_y: self.home_y,
+            health: self.health,
+            monster_type: self.monster_type,
+            affix: self.affix,
+            pack: self.pack,
+        }
+    }
+
+    pub fn from_state(state: &MonsterState, difficulty: Difficulty) -> Self {
+        let mut monster = match state.affix {
+            Some(affix) => Self::new_elite(state.home_x, state.home_y, state.monster_type, affix, difficulty),
+            None => Self::new(state.home_x, state.home_y, state.monster_type, difficulty),
+        };
+        monster.x = state.x;
+        monster.y = state.y;
+        monster.pack = state.pack;
+        monster.health = state.health.min(monster.max_health);
+        monster
+    }
+
+    /// `flock` is None for a monster without packmates nearby, which then chases on its own
+    pub fn update(&mut self, dt: f32, player_x: f32, player_y: f32, world: &World, flock: Option<Flock>) {
+        let (old_x, old_y) = (self.x, self.y);
+        self.think(dt, player_x, player_y, world, flock);
+        if dt > 0.0 {
+            self.vx = (self.x - old_x) / dt;
+            self.vy = (self.y - old_y) / dt;
         }
     }
 
-    pub fn update(&mut self, dt: f32, player_x: f32, player_y: f32) {
+    fn think(&mut self, dt: f32, player_x: f32, player_y: f32, world: &World, flock: Option<Flock>) {
         // Attack cooldown
         if self.attack_cooldown > 0.0 {
             self.attack_cooldown -= dt;
         }
+        self.repath_timer -= dt;
+        self.hit_flash_timer = (self.hit_flash_timer - dt).max(0.0);
+        self.ranged_cooldown = (self.ranged_cooldown - dt).max(0.0);
+        self.stun_immunity = (self.stun_immunity - dt).max(0.0);
+        self.fleeing = false;
+        if self.stun_timer > 0.0 {
+            self.stun_timer -= dt;
+            return;
+        }
 
-        // Chase player if within detection range (10 tiles)
         let dx = player_x - self.x;
         let dy = player_y - self.y;
         let dist = (dx * dx + dy * dy).sqrt();
+        // Walkers need a clear line to notice the player, then remember them briefly once it's broken;
+        // flyers look down over everything. The sight check only runs for players in range.
+        let in_range = dist <= self.monster_type.detection_range();
+        let sees = in_range && (self.monster_type.is_flying() || world.line_of_sight(self.x, self.y, player_x, player_y));
+        self.sight_memory = if sees { Self::SIGHT_MEMORY } else { (self.sight_memory - dt).max(0.0) };
+        let in_detection = in_range && self.sight_memory > 0.0;
 
-        if dist <= 10.0 && dist > 0.5 {
-            // Move toward player
-            let move_x = (dx / dist) * self.speed * dt;
-            let move_y = (dy / dist) * self.speed * dt;
-            self.x += move_x;
-            self.y += move_y;
+        if self.returning {
+            // Re-engage only if the player steps back into this monster's territory,
+            // otherwise a player standing at the leash edge would flip it back and forth
+            let player_from_home = ((player_x - self.home_x).powi(2) + (player_y - self.home_y).powi(2)).sqrt();
+            if in_detection && player_from_home <= self.leash_distance {
+                self.returning = false;
+                self.path.clear();
+            } else {
+                self.return_home(dt, world);
+                return;
+            }
+        }
+
+        // Hurt cowards run while they can; one with nowhere to go turns and fights
+        if in_detection
+            && self.monster_type.flees_when_low()
+            && self.health_fraction() < Self::FLEE_HEALTH
+            && self.flee(dt, player_x, player_y, world)
+        {
+            self.fleeing = true;
+            self.path.clear();
+            // Running doesn't get past the leash: too far out, it heads home like any chaser
+            if self.distance_from_home() > self.leash_distance {
+                self.returning = true;
+            }
+            return;
+        }
+
+        // Chase player if within detection range
+        if in_detection && dist > 0.5 + self.monster_type.footprint_radius() {
+            // Packmates in the open steer as a group; detours and blocked steps chase the player directly
+            let steered = flock.filter(|_| self.path.is_empty()).map(|f| self.flock_target(f, player_x, player_y, dist));
+            let moved = steered.is_some_and(|(x, y)| self.step_toward(x, y, self.speed, dt, world).is_some());
+            if !moved {
+                self.chase(dt, player_x, player_y, world);
+            }
+
+            // Dragged too far from home: give up and walk back
+            if self.distance_from_home() > self.leash_distance {
+                self.returning = true;
+                self.path.clear();
+            }
+        } else {
+            self.path.clear();
+            if !in_detection {
+                self.wander(dt, world);
+            }
         }
     }
 
+    /// Drift slowly between random points near home while no player is around
+    fn wander(&mut self, dt: f32, world: &World) {
+        self.wander_timer -= dt;
+        if self.wander_timer <= 0.0 {
+            self.wander_timer = rand::gen_range(3.0, 6.0);
+            let angle = rand::gen_range(0.0, std::f32::consts::TAU);
+            let radius = rand::gen_range(0.5, Self::WANDER_RADIUS);
+            self.wander_target = Some((self.home_x + angle.cos() * radius, self.home_y + angle.sin() * radius));
+        }
+
+        if let Some((target_x, target_y)) = self.wander_target {
+            let speed = self.speed * Self::WANDER_SPEED_FACTOR;
+            // Stop at the destination, or give up if something solid is in the way
+            if self.step_toward(target_x, target_y, speed, dt, world) != Some(false) {
+                self.wander_target = None;
+            }
+        }
+    }
+
+    /// Point to chase instead of the player, bent toward the pack's center and heading. The blend
+    /// is a direction only, so however the pack moves nobody goes faster than their own speed.
+    fn flock_target(&self, flock: Flock, player_x: f32, player_y: f32, dist: f32) -> (f32, f32) {
+        // Each pull is capped at the player's weight of 1 before weighting, then fades near the player
+        let capped = |x: f32, y: f32| {
+            let length = (x * x + y * y).sqrt();
+            if length > 1.0 { (x / length, y / length) } else { (x, y) }
+        };
+        let fade = (dist / Flock::ENGAGE_DISTANCE).min(1.0);
+        let (cohesion_x, cohesion_y) = capped(flock.center_x - self.x, flock.center_y - self.y);
+        let (align_x, align_y) = capped(flock.heading_x / self.speed, flock.heading_y / self.speed);
+
+        let steer_x = (player_x - self.x) / dist + fade * (Flock::COHESION * cohesion_x + Flock::ALIGNMENT * align_x);
+        let steer_y = (player_y - self.y) / dist + fade * (Flock::COHESION * cohesion_y + Flock::ALIGNMENT * align_y);
+        let steer_length = (steer_x * steer_x + steer_y * steer_y).sqrt();
+        if steer_length < 0.01 {
+            return (player_x, player_y); // Pulls cancel out: fall back to a straight chase
+        }
+        (self.x + steer_x / steer_length * dist, self.y + steer_y / steer_length * dist)
+    }
+
+    /// Step directly away from the player, or slide along whatever is in the way at 45 or 90 degrees.
+    /// False when every way is blocked - the monster is cornered.
+    fn flee(&mut self, dt: f32, player_x: f32, player_y: f32, world: &World) -> bool {
+        let away = (self.y - player_y).atan2(self.x - player_x);
+        let quarter = std::f32::consts::FRAC_PI_4;
+        [0.0, quarter, -quarter, 2.0 * quarter, -2.0 * quarter].into_iter().any(|turn| {
+            let angle = away + turn;
+            let (target_x, target_y) = (self.x + angle.cos(), self.y + angle.sin());
+            self.step_toward(target_x, target_y, self.speed, dt, world).is_some()
+        })
+    }
+
+    fn distance_from_home(&self) -> f32 {
+        let dx = self.home_x - self.x;
+        let dy = self.home_y - self.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    /// Walk back to the spawn point, healing 10% of max health every half second on the way
+    fn return_home(&mut self, dt: f32, world: &World) {
+        self.regen_timer += dt;
+        if self.regen_timer >= 0.5 {
+            self.regen_timer -= 0.5;
+            self.health = (self.health + (self.max_health / 10).max(1)).min(self.max_health);
+        }
+
+        if self.distance_from_home() <= 0.5 {
+            self.returning = false;
+            self.regen_timer = 0.0;
+            self.path.clear();
+            return;
+        }
+        self.chase(dt, self.home_x, self.home_y, world);
+    }
+
+    /// Move toward a target, detouring along an A* path when the straight line is blocked
+    fn chase(&mut self, dt: f32, target_x: f32, target_y: f32, world: &World) {
+        // Follow the current detour first
+        if let Some(&(waypoint_x, waypoint_y)) = self.path.last() {
+            let reached = self.step_toward(waypoint_x as f32, waypoint_y as f32, self.speed, dt, world);
+            match reached {
+                Some(true) => {
+                    self.path.pop();
+                }
+                Some(false) => {}
+                None => self.path.clear(), // World changed under us - replan next time
+            }
+            return;
+        }
+
+        if self.step_toward(target_x, target_y, self.speed, dt, world).is_none() && self.repath_timer <= 0.0 {
+            // Straight line is blocked: plan a detour rather than pushing into the wall
+            self.repath_timer = 0.5;
+            let start = (self.x.round() as i32, self.y.round() as i32);
+            let goal = (target_x.round() as i32, target_y.round() as i32);
+            // Search a little past the goal so far-off targets (like home) stay reachable
+            let radius = ((start.0 - goal.0).abs().max((start.1 - goal.1).abs()) + 4).clamp(12, 24);
+            if let Some(mut path) = world.find_path(start, goal, radius) {
+                path.reverse();
+                self.path = path;
+            }
+        }
+    }
+
+    /// Take one step toward a point. Returns Some(true) if the point was reached,
+    /// Some(false) if moving, or None if the step would enter a solid tile.
+    fn step_toward(&mut self, target_x: f32, target_y: f32, speed: f32, dt: f32, world: &World) -> Option<bool> {
+        let dx = target_x - self.x;
+        let dy = target_y - self.y;
+        let dist = (dx * dx + dy * dy).sqrt();
+        if dist < 0.05 {
+            return Some(true);
+        }
+
+        // Don't overshoot the target
+        let step = (speed * dt).min(dist);
+        let new_x = self.x + dx / dist * step;
+        let new_y = self.y + dy / dist * step;
+
+        // Monsters that spawned inside a solid tile may always walk out of it
+        if !self.monster_type.is_flying() && world.is_blocked(new_x, new_y) && !world.is_blocked(self.x, self.y) {
+            return None;
+        }
+
+        self.x = new_x;
+        self.y = new_y;
+        Some(step >= dist)
+    }
+
     pub fn can_attack(&self) -> bool {
-        self.attack_cooldown <= 0.0
+        self.attack_cooldown <= 0.0 && !self.is_stunned() && !self.fleeing
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.stun_timer > 0.0
+    }
+
+    /// Stun for a short time; ignored while still immune from the last stun
+    pub fn stun(&mut self) {
+        if self.stun_immunity <= 0.0 {
+            self.stun_timer = Self::STUN_DURATION;
+            self.stun_immunity = Self::STUN_DURATION + Self::STUN_IMMUNITY;
+        }
+    }
+
+    /// Back off to home and hold every attack for `secs` (used around a respawning player)
+    pub fn hold_off(&mut self, secs: f32) {
+        self.attack_cooldown = self.attack_cooldown.max(secs);
+        self.ranged_cooldown = self.ranged_cooldown.max(secs);
+        self.returning = true;
+        self.path.clear();
     }
 
     pub fn attack(&mut self) {
-        self.attack_cooldown = 0.5; // Monsters attack every 0.5 seconds
+        self.attack_cooldown = self.monster_type.attack_cooldown();
+    }
+
+    /// Roll one melee hit's raw damage; the spread scales with difficulty like the base does
+    pub fn calculate_damage(&self, rng: &mut GameRng) -> i32 {
+        let spread = difficulty::scale(self.monster_type.damage_spread(), self.difficulty.damage_multiplier());
+        rng.gen_range(self.damage - spread, self.damage + spread + 1).max(1)
+    }
+
+    /// Breathe fire at the player's current position when in range but not adjacent
+    pub fn try_ranged_attack(&mut self, player_x: f32, player_y: f32) -> Option<Projectile> {
+        if !self.monster_type.is_ranged() || self.returning || self.is_stunned() || self.ranged_cooldown > 0.0 {
+            return None;
+        }
+
+        let dx = player_x - self.x;
+        let dy = player_y - self.y;
+        let dist = (dx * dx + dy * dy).sqrt();
+        // Close enough to claw instead
+        if dist <= 1.0 + self.monster_type.footprint_radius() || dist > Self::BREATH_RANGE {
+            return None;
+        }
+
+        self.ranged_cooldown = Self::BREATH_COOLDOWN;
+        Some(Projectile {
+            x: self.x,
+            y: self.y,
+            vx: dx / dist * Self::BREATH_SPEED,
+            vy: dy / dist * Self::BREATH_SPEED,
+            damage: self.damage * 4 / 5,
+            damage_type: DamageType::Fire,
+            lifetime: Self::BREATH_RANGE * 1.5 / Self::BREATH_SPEED,
+        })
     }
 
-    pub fn calculate_damage(&self, player: &Player) -> i32 {
-        calculate_damage(self.monster_type.base_damage(), player.armor.as_ref())
+    /// Health as a fraction of max, clamped to 0..=1 (and safe if max were ever 0)
+    pub fn health_fraction(&self) -> f32 {
+        (self.health as f32 / self.max_health.max(1) as f32).clamp(0.0, 1.0)
     }
 
     pub fn take_damage(&mut self, damage: i32) {
         self.health = (self.health - damage).max(0);
+        self.hit_flash_timer = Self::HIT_FLASH_DURATION;
     }
 
-    pub fn roll_loot(&self) -> Option<Item> {
-        if rand::gen_range(0.0, 1.0) < 0.25 {
-            // 25% drop rate
-            Some(Item::random())
+    pub fn roll_loot(&self, rng: &mut GameRng) -> Option<Item> {
+        // Drop rate comes from the type's table, doubled for elites and raised on harder difficulties
+        let table = self.monster_type.loot_table();
+        let mut drop_chance = table.drop_chance * self.difficulty.loot_multiplier();
+        if self.affix.is_some() {
+            drop_chance *= 2.0;
+        }
+        // Uniques come only from elites of the big types, and rarely even then
+        if self.affix.is_some() && self.monster_type.drops_uniques() && rng.gen_f32() < Self::UNIQUE_CHANCE {
+            let unique = &UNIQUES[rng.gen_range(0, UNIQUES.len() as i32) as usize];
+            return Some(unique.to_item());
+        }
+        if rng.gen_f32() < drop_chance {
+            table.pick(rng)
         } else {
             None
         }
     }
 
-    pub fn draw(&self, camera: &GameCamera) {
-        let (screen_x, screen_y) = camera.world_to_screen(self.x, self.y);
+    /// Experience for the kill - scales with toughness, elites double
+    pub fn xp_reward(&self) -> u32 {
+        let xp = (self.monster_type.max_health() / 2 + self.monster_type.base_damage()) as u32;
+        if self.affix.is_some() { xp * 2 } else { xp }
+    }
 
-        let color = self.monster_type.color();
+    /// Gold dropped on death - tougher monsters pay more, elites double
+    pub fn roll_gold(&self, rng: &mut GameRng) -> u32 {
+        let gold = rng.gen_range(2, 6) + self.monster_type.max_health() / 10;
+        let gold = difficulty::scale(gold, self.difficulty.loot_multiplier()) as u32;
+        if self.affix.is_some() { gold * 2 } else { gold }
+    }
+
+    /// Screen pixels the body is drawn above its ground position; flyers bob gently in the air
+    pub fn body_lift(&self) -> f32 {
+        if !self.monster_type.is_flying() {
+            return 0.0;
+        }
+        Self::FLIGHT_HEIGHT + (get_time() as f32 * 3.0 + self.home_x).sin() * 2.0
+    }
+
+    /// Draw the monster; `light` (0-1) dims it outside the player's vision
+    pub fn draw(&self, camera: &GameCamera, light: f32, palette: Palette) {
+        let (screen_x, ground_y) = camera.world_to_screen(self.x, self.y);
         let size = self.monster_type.size();
 
+        // Shadow stays on the ground; flyers are drawn lifted above it
+        draw_shadow(screen_x, ground_y, size * 0.9);
+        let screen_y = ground_y - self.body_lift();
+
+        let mut color = shade(self.monster_type.color(), light);
+        // Fade from white back to the body color over the flash
+        let flash = self.hit_flash_timer / Self::HIT_FLASH_DURATION;
+        if flash > 0.0 {
+            color = Color::new(
+                color.r + (1.0 - color.r) * flash,
+                color.g + (1.0 - color.g) * flash,
+                color.b + (1.0 - color.b) * flash,
+                color.a,
+            );
+        }
+
+        // Elite aura on the ground beneath the body
+        if let Some(affix) = self.affix {
+            let aura = shade(affix.aura_color(), light);
+            let fill = Color::new(aura.r, aura.g, aura.b, 0.35);
+            draw_ellipse(screen_x, ground_y, size * 1.3, size * 0.65, 0.0, fill);
+            draw_ellipse_lines(screen_x, ground_y, size * 1.3, size * 0.65, 0.0, 2.0, aura);
+        }
+
         match self.monster_type {
             MonsterType::Goblin | MonsterType::SnowGoblin => {
                 self.draw_goblin(screen_x, screen_y, color, size);
@@ -176,8 +888,19 @@ impl Monster {
             draw_rectangle(bar_x, bar_y, bar_width, bar_height, DARKGRAY);
 
             // Health
-            let health_pct = self.health as f32 / self.max_health as f32;
-            draw_rectangle(bar_x, bar_y, bar_width * health_pct, bar_height, RED);
+            let health_pct = self.health_fraction();
+            draw_rectangle(bar_x, bar_y, bar_width * health_pct, bar_height, palette.bad());
+        }
+
+        // Stars circling above the head while stunned
+        if self.is_stunned() {
+            let spin = get_time() as f32 * 4.0;
+            for i in 0..3 {
+                let angle = spin + i as f32 * std::f32::consts::TAU / 3.0;
+                let star_x = screen_x + angle.cos() * size * 0.7;
+                let star_y = screen_y - size - 24.0 + angle.sin() * 3.0;
+                draw_poly(star_x, star_y, 5, 3.5, angle.to_degrees(), YELLOW);
+            }
         }
     }
 
@@ -420,3 +1143,23 @@ impl Monster {
         draw_line(right_hand_x, right_hand_y, right_hand_x + 2.0, right_hand_y + 7.0, 2.0, claw_color);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn health_fraction_is_finite_and_clamped() {
+        let mut monster = Monster::new(0.0, 0.0, MonsterType::Goblin, Difficulty::Normal);
+        monster.max_health = 0;
+        let fraction = monster.health_fraction();
+        assert!(fraction.is_finite() && (0.0..=1.0).contains(&fraction));
+
+        // Overhealed and negative health both stay in range
+        monster.max_health = 10;
+        monster.health = 25;
+        assert_eq!(monster.health_fraction(), 1.0);
+        monster.health = -5;
+        assert_eq!(monster.health_fraction(), 0.0);
+    }
+}
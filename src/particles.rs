@@ -0,0 +1,248 @@
+use macroquad::prelude::*;
+use std::collections::VecDeque;
+
+use crate::camera::GameCamera;
+use crate::world::Terrain;
+
+/// Short-lived blood/gib fleck. Position is in world tiles plus a height above the ground,
+/// so gravity pulls it back down onto the isometric floor instead of straight down the screen.
+pub struct Particle {
+    pub pos: Vec2,
+    pub height: f32, // Pixels above the ground
+    pub vel: Vec2,   // Tiles per second across the ground
+    pub vertical_speed: f32,
+    pub lifetime: f32,
+    max_lifetime: f32,
+    pub color: Color,
+}
+
+impl Particle {
+    const GRAVITY: f32 = 400.0; // Pixels per second squared
+    const GROUND_FRICTION: f32 = 6.0;
+
+    /// Advance the particle; returns false once it has faded out
+    pub fn update(&mut self, dt: f32) -> bool {
+        self.vertical_speed -= Self::GRAVITY * dt;
+        self.height += self.vertical_speed * dt;
+        if self.height <= 0.0 {
+            // Landed: stop bouncing and slide to a halt
+            self.height = 0.0;
+            self.vertical_speed = 0.0;
+            self.vel *= (1.0 - Self::GROUND_FRICTION * dt).max(0.0);
+        }
+        self.pos += self.vel * dt;
+        self.lifetime -= dt;
+        self.lifetime > 0.0
+    }
+
+    pub fn draw(&self, camera: &GameCamera) {
+        let (screen_x, screen_y) = camera.world_to_screen(self.pos.x, self.pos.y);
+        let alpha = (self.lifetime / self.max_lifetime).clamp(0.0, 1.0);
+        let color = Color::new(self.color.r, self.color.g, self.color.b, self.color.a * alpha);
+        draw_circle(screen_x, screen_y - self.height, 2.5, color);
+    }
+}
+
+/// Spray of particles in the monster's own color mixed with blood red
+pub fn death_burst(x: f32, y: f32, color: Color, count: usize) -> impl Iterator<Item = Particle> {
+    let blood = Color::from_rgba(150, 10, 10, 255);
+    (0..count).map(move |i| {
+        let angle = rand::gen_range(0.0, std::f32::consts::TAU);
+        let speed = rand::gen_range(0.5, 2.5);
+        let lifetime = rand::gen_range(0.6, 1.1);
+        Particle {
+            pos: vec2(x, y),
+            height: 16.0,
+            vel: vec2(angle.cos(), angle.sin()) * speed,
+            vertical_speed: rand::gen_range(60.0, 160.0),
+            lifetime,
+            max_lifetime: lifetime,
+            color: if i % 3 == 0 { blood } else { color },
+        }
+    })
+}
+
+/// Fading prints left behind while walking on snow, oldest at the front
+pub struct Footprints {
+    marks: VecDeque<Footprint>,
+    last: Option<Vec2>, // Where the previous print was left
+    left_foot: bool,
+}
+
+struct Footprint {
+    pos: Vec2,
+    age: f32,
+}
+
+impl Footprints {
+    const STRIDE: f32 = 0.45; // Tiles walked between prints
+    const FOOT_OFFSET: f32 = 0.08; // Sideways offset so left and right prints alternate
+    const LIFETIME: f32 = 4.0;
+    const MAX_MARKS: usize = 48;
+
+    pub fn new() -> Self {
+        Self { marks: VecDeque::new(), last: None, left_foot: false }
+    }
+
+    /// Age the prints, and leave a new one each stride while `on_snow`
+    pub fn update(&mut self, dt: f32, x: f32, y: f32, on_snow: bool) {
+        for mark in &mut self.marks {
+            mark.age += dt;
+        }
+        while self.marks.front().is_some_and(|m| m.age >= Self::LIFETIME) {
+            self.marks.pop_front();
+        }
+
+        let pos = Vec2::new(x, y);
+        if !on_snow {
+            self.last = None;
+            return;
+        }
+        let Some(last) = self.last else {
+            self.last = Some(pos);
+            return;
+        };
+        let step = pos - last;
+        if step.length() < Self::STRIDE {
+            return;
+        }
+
+        let side = if self.left_foot { 1.0 } else { -1.0 };
+        let offset = step.normalize().perp() * Self::FOOT_OFFSET * side;
+        if self.marks.len() >= Self::MAX_MARKS {
+            self.marks.pop_front();
+        }
+        self.marks.push_back(Footprint { pos: pos + offset, age: 0.0 });
+        self.left_foot = !self.left_foot;
+        self.last = Some(pos);
+    }
+
+    pub fn draw(&self, camera: &GameCamera) {
+        for mark in &self.marks {
+            let (screen_x, screen_y) = camera.world_to_screen(mark.pos.x, mark.pos.y);
+            let alpha = 0.3 * (1.0 - mark.age / Self::LIFETIME);
+            draw_ellipse(screen_x, screen_y, 4.0, 2.0, 0.0, Color::new(0.3, 0.35, 0.45, alpha));
+        }
+    }
+}
+
+/// Screen-space ambience for the biome underfoot: snowfall on snow, heat shimmer in the desert.
+/// Each kind has a strength that eases toward 1 in its biome and 0 elsewhere, scaling both how
+/// fast particles appear and how opaque they are, so crossing a border fades rather than pops.
+pub struct Weather {
+    particles: Vec<WeatherParticle>,
+    snow: f32, // Current strength, 0..1
+    heat: f32,
+    spawn_budget: f32, // Fractional particles owed from previous frames
+    time: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum WeatherKind {
+    Snow,
+    Heat,
+}
+
+struct WeatherParticle {
+    kind: WeatherKind,
+    pos: Vec2, // Pixels
+    vel: Vec2, // Pixels per second
+    size: f32,
+    phase: f32, // Offsets the sway so particles don't move in lockstep
+    lifetime: f32,
+    max_lifetime: f32,
+}
+
+impl Weather {
+    const MAX_PARTICLES: usize = 160;
+    const FADE_RATE: f32 = 1.5; // Strength easing, per second
+    const SNOW_RATE: f32 = 60.0; // Flakes per second at full strength
+    const HEAT_RATE: f32 = 14.0;
+
+    pub fn new() -> Self {
+        Self { particles: Vec::new(), snow: 0.0, heat: 0.0, spawn_budget: 0.0, time: 0.0 }
+    }
+
+    /// Ease toward `terrain`'s weather (or none when `enabled` is off) and move the particles
+    pub fn update(&mut self, dt: f32, terrain: Terrain, enabled: bool) {
+        self.time += dt;
+        let t = 1.0 - (-Self::FADE_RATE * dt).exp();
+        let target_snow = if enabled && terrain == Terrain::Snow { 1.0 } else { 0.0 };
+        let target_heat = if enabled && terrain == Terrain::Desert { 1.0 } else { 0.0 };
+        self.snow += (target_snow - self.snow) * t;
+        self.heat += (target_heat - self.heat) * t;
+
+        let (w, h) = (screen_width(), screen_height());
+        let time = self.time;
+        self.particles.retain_mut(|p| {
+            p.lifetime -= dt;
+            p.pos += p.vel * dt;
+            if p.kind == WeatherKind::Snow {
+                p.pos.x += (time * 1.5 + p.phase).sin() * 20.0 * dt; // Sway as it falls
+            }
+            p.lifetime > 0.0 && p.pos.y < h + 10.0 && p.pos.x > -10.0 && p.pos.x < w + 10.0
+        });
+
+        self.spawn_budget += (self.snow * Self::SNOW_RATE + self.heat * Self::HEAT_RATE) * dt;
+        while self.spawn_budget >= 1.0 {
+            self.spawn_budget -= 1.0;
+            if self.particles.len() >= Self::MAX_PARTICLES {
+                continue;
+            }
+            // Pick the kind in proportion to how strong each is, so a border crossing blends them
+            let snow_share = self.snow * Self::SNOW_RATE;
+            let heat_share = self.heat * Self::HEAT_RATE;
+            let is_snow = rand::gen_range(0.0, snow_share + heat_share) < snow_share;
+            self.particles.push(if is_snow { Self::flake(w) } else { Self::shimmer(w, h) });
+        }
+    }
+
+    /// Snowflake entering above the top edge
+    fn flake(w: f32) -> WeatherParticle {
+        let lifetime = 12.0; // Long enough to cross the screen; leaving the bottom removes it first
+        WeatherParticle {
+            kind: WeatherKind::Snow,
+            pos: vec2(rand::gen_range(-10.0, w + 10.0), -5.0),
+            vel: vec2(rand::gen_range(-15.0, 5.0), rand::gen_range(50.0, 110.0)),
+            size: rand::gen_range(1.2, 2.8),
+            phase: rand::gen_range(0.0, std::f32::consts::TAU),
+            lifetime,
+            max_lifetime: lifetime,
+        }
+    }
+
+    /// Rising ripple of hot air, mostly over the lower half of the screen
+    fn shimmer(w: f32, h: f32) -> WeatherParticle {
+        let lifetime = rand::gen_range(1.5, 3.0);
+        WeatherParticle {
+            kind: WeatherKind::Heat,
+            pos: vec2(rand::gen_range(0.0, w), rand::gen_range(h * 0.35, h)),
+            vel: vec2(0.0, rand::gen_range(-25.0, -10.0)),
+            size: rand::gen_range(18.0, 40.0), // Half-width of the ripple
+            phase: rand::gen_range(0.0, std::f32::consts::TAU),
+            lifetime,
+            max_lifetime: lifetime,
+        }
+    }
+
+    pub fn draw(&self) {
+        for p in &self.particles {
+            match p.kind {
+                WeatherKind::Snow => {
+                    let alpha = 0.8 * self.snow;
+                    draw_circle(p.pos.x, p.pos.y, p.size, Color::new(1.0, 1.0, 1.0, alpha));
+                }
+                WeatherKind::Heat => {
+                    // Fades in and out over its life, peaking halfway
+                    let life = p.lifetime / p.max_lifetime;
+                    let alpha = 0.12 * self.heat * (life * std::f32::consts::PI).sin();
+                    let color = Color::new(1.0, 0.9, 0.7, alpha);
+                    let wave = (self.time * 3.0 + p.phase).sin() * 2.0;
+                    let (x, y) = (p.pos.x, p.pos.y);
+                    draw_line(x - p.size, y + wave, x, y - wave, 2.0, color);
+                    draw_line(x, y - wave, x + p.size, y + wave, 2.0, color);
+                }
+            }
+        }
+    }
+}
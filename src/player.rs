@@ -1,26 +1,74 @@
 use macroquad::prelude::*;
 
-use crate::camera::GameCamera;
-use crate::combat::{ArmorType, Item, WeaponType};
+use crate::camera::{GameCamera, TILE_HEIGHT, TILE_WIDTH};
+use crate::combat::{angle_between, Armor, ArmorType, DamageType, Item, Weapon, WeaponType, FIST_COOLDOWN, FIST_DAMAGE};
 use crate::inventory::Inventory;
-use crate::world::World;
+use crate::rng::GameRng;
+use crate::world::{draw_shadow, World};
 
-/// The four isometric directions the player can face
+/// Melee reach in tiles
+pub const ATTACK_RANGE: f32 = 1.0;
+/// Half-width of the swing arc - monsters within 45 degrees either side of facing are hit
+pub const ATTACK_CONE_HALF_ANGLE: f32 = std::f32::consts::FRAC_PI_4;
+
+/// The eight isometric directions the player can face: the four single-key diagonals plus the
+/// in-betweens a pair of neighbouring keys gives
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Direction {
     UpLeft,    // W key - toward top-left of screen
     UpRight,   // D key - toward top-right of screen
     DownLeft,  // A key - toward bottom-left of screen
     DownRight, // S key - toward bottom-right of screen
+    Up,        // W+D - between up-left and up-right
+    Down,      // A+S - between down-left and down-right
+    Left,      // W+A - between up-left and down-left
+    Right,     // S+D - between up-right and down-right
 }
 
 impl Direction {
+    /// Clockwise from Right (world +x), one per 45 degree sector
+    const SECTORS: [Direction; 8] = [
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+        Direction::Up,
+        Direction::UpRight,
+    ];
+
+    /// Direction nearest to a world-space vector.
+    /// In isometric: dx < 0 means toward the left side of the screen, dy < 0 toward the top.
+    pub fn from_vector(dx: f32, dy: f32) -> Direction {
+        let sector = (dy.atan2(dx) / std::f32::consts::FRAC_PI_4).round() as i32;
+        Self::SECTORS[sector.rem_euclid(8) as usize]
+    }
+
+    /// Unit facing vector in world space (matches the WASD movement vectors)
+    pub fn world_vector(&self) -> (f32, f32) {
+        let d = std::f32::consts::FRAC_1_SQRT_2;
+        match self {
+            Direction::UpLeft => (-d, -d),
+            Direction::UpRight => (d, -d),
+            Direction::DownLeft => (-d, d),
+            Direction::DownRight => (d, d),
+            Direction::Up => (0.0, -1.0),
+            Direction::Down => (0.0, 1.0),
+            Direction::Left => (-1.0, 0.0),
+            Direction::Right => (1.0, 0.0),
+        }
+    }
+
     /// Returns the weapon offset (x, y) relative to player center for this direction
-    /// Weapon always points UP - left/right based on horizontal movement direction
+    /// Weapon always points UP - left/right based on horizontal movement direction.
+    /// The in-betweens lean a little further out and lower, so they read as turned.
     pub fn weapon_offset(&self) -> (f32, f32) {
         match self {
             Direction::UpLeft | Direction::DownLeft => (-15.0, -20.0),   // Left hand, pointing up
             Direction::UpRight | Direction::DownRight => (15.0, -20.0),  // Right hand, pointing up
+            Direction::Left | Direction::Down => (-17.0, -18.0),
+            Direction::Up | Direction::Right => (17.0, -18.0),
         }
     }
 
@@ -30,6 +78,8 @@ impl Direction {
         match self {
             Direction::UpLeft | Direction::DownLeft => (-30.0, -35.0),   // Left hand, pointing up
             Direction::UpRight | Direction::DownRight => (30.0, -35.0),  // Right hand, pointing up
+            Direction::Left | Direction::Down => (-34.0, -32.0),
+            Direction::Up | Direction::Right => (34.0, -32.0),
         }
     }
 
@@ -39,66 +89,359 @@ impl Direction {
         match self {
             Direction::UpLeft | Direction::DownLeft => (-25.0, -30.0),   // Left side, above
             Direction::UpRight | Direction::DownRight => (25.0, -30.0),  // Right side, above
+            Direction::Left | Direction::Down => (-29.0, -27.0),
+            Direction::Up | Direction::Right => (29.0, -27.0),
+        }
+    }
+}
+
+/// Timed effect applied to the player
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatusEffect {
+    Poison { dps: f32, remaining: f32 },
+}
+
+impl StatusEffect {
+    pub fn is_expired(&self) -> bool {
+        match self {
+            StatusEffect::Poison { remaining, .. } => *remaining <= 0.0,
+        }
+    }
+}
+
+/// Attribute a level-up point can be spent on
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stat {
+    Strength,  // Flat bonus damage
+    Vitality,  // More max health
+    Dexterity, // Faster swings
+    Magnetism, // Wider pickup reach, and loot drifts in from further
+}
+
+impl Stat {
+    pub const ALL: [Stat; 4] = [Stat::Strength, Stat::Vitality, Stat::Dexterity, Stat::Magnetism];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Stat::Strength => "Strength",
+            Stat::Vitality => "Vitality",
+            Stat::Dexterity => "Dexterity",
+            Stat::Magnetism => "Magnetism",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Stat::Strength => format!("+{} damage per hit", Player::STRENGTH_DAMAGE),
+            Stat::Vitality => format!("+{} max health", Player::VITALITY_HEALTH),
+            Stat::Dexterity => format!("{:.0}% faster attacks", Player::DEXTERITY_SPEED * 100.0),
+            Stat::Magnetism => format!(
+                "+{} pickup range, pulls loot from {} tiles further",
+                Player::MAGNETISM_PICKUP_RANGE,
+                Player::MAGNETISM_PULL_RANGE
+            ),
+        }
+    }
+}
+
+/// Loadout a new run starts with, picked in the main menu and saved with the run
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StartingClass {
+    Warrior,
+    Rogue,
+    Mage,
+}
+
+impl StartingClass {
+    pub const ALL: [StartingClass; 3] = [StartingClass::Warrior, StartingClass::Rogue, StartingClass::Mage];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StartingClass::Warrior => "Warrior",
+            StartingClass::Rogue => "Rogue",
+            StartingClass::Mage => "Mage",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<StartingClass> {
+        Self::ALL.into_iter().find(|c| c.name() == name)
+    }
+
+    /// Next class in menu order, wrapping around (`step` is +1 or -1)
+    pub fn cycle(self, step: i32) -> StartingClass {
+        let index = Self::ALL.iter().position(|&c| c == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+
+    fn max_health(&self) -> i32 {
+        match self {
+            StartingClass::Warrior => 50,
+            StartingClass::Rogue => 40, // Fights from range, so starts frailer
+            StartingClass::Mage => 35,
+        }
+    }
+
+    fn max_mana(&self) -> u32 {
+        match self {
+            StartingClass::Warrior | StartingClass::Rogue => 0,
+            StartingClass::Mage => 50,
+        }
+    }
+
+    // Kits are handed out as starter gear, so they equip at level 1 whatever the type asks for
+    fn weapon(&self) -> WeaponType {
+        match self {
+            StartingClass::Warrior => WeaponType::Mace,
+            StartingClass::Rogue => WeaponType::Bow,
+            StartingClass::Mage => WeaponType::Staff,
+        }
+    }
+
+    fn armor(&self) -> Option<ArmorType> {
+        match self {
+            StartingClass::Warrior => Some(ArmorType::Platemail),
+            StartingClass::Rogue => Some(ArmorType::Leather),
+            StartingClass::Mage => None, // Robes only: trades armor for mana
         }
     }
 }
 
+/// Held movement keys as an unnormalized world-space direction, adjusted for the isometric view:
+/// W/Up = up-left, S/Down = down-right, A/Left = down-left, D/Right = up-right
+pub fn movement_input() -> (f32, f32) {
+    let mut dx: f32 = 0.0;
+    let mut dy: f32 = 0.0;
+    if is_key_down(KeyCode::W) || is_key_down(KeyCode::Up) {
+        dx -= 1.0;
+        dy -= 1.0;
+    }
+    if is_key_down(KeyCode::S) || is_key_down(KeyCode::Down) {
+        dx += 1.0;
+        dy += 1.0;
+    }
+    if is_key_down(KeyCode::A) || is_key_down(KeyCode::Left) {
+        dx -= 1.0;
+        dy += 1.0;
+    }
+    if is_key_down(KeyCode::D) || is_key_down(KeyCode::Right) {
+        dx += 1.0;
+        dy -= 1.0;
+    }
+    (dx, dy)
+}
+
+/// Arrow loosed from a bow; it hits the first monster in its way, or falls away at the end of its range
+pub struct Arrow {
+    pub x: f32,
+    pub y: f32,
+    vx: f32, // Tiles per second
+    vy: f32,
+    pub damage: i32, // Rolled when loosed, strength and combo included
+    range_left: f32,
+}
+
+impl Arrow {
+    const SPEED: f32 = 16.0;
+    pub const RANGE: f32 = 8.0;
+    const HIT_RADIUS: f32 = 0.3;
+
+    /// Loose an arrow from (x, y) along a unit direction
+    fn new(x: f32, y: f32, (dir_x, dir_y): (f32, f32), damage: i32) -> Self {
+        Self { x, y, vx: dir_x * Self::SPEED, vy: dir_y * Self::SPEED, damage, range_left: Self::RANGE }
+    }
+
+    /// Advance the arrow; returns false once it's spent its range or struck something solid
+    pub fn update(&mut self, dt: f32, world: &World) -> bool {
+        let step = (Self::SPEED * dt).min(self.range_left);
+        self.x += self.vx / Self::SPEED * step;
+        self.y += self.vy / Self::SPEED * step;
+        self.range_left -= step;
+        self.range_left > 0.0 && !world.is_blocked(self.x, self.y)
+    }
+
+    /// Whether the arrow touches a body of `radius` tiles centered at (x, y)
+    pub fn hits(&self, x: f32, y: f32, radius: f32) -> bool {
+        let (dx, dy) = (x - self.x, y - self.y);
+        let reach = radius + Self::HIT_RADIUS;
+        dx * dx + dy * dy <= reach * reach
+    }
+
+    pub fn draw(&self, camera: &GameCamera) {
+        let (screen_x, screen_y) = camera.world_to_screen(self.x, self.y);
+        let (tail_x, tail_y) = camera.world_to_screen(self.x - self.vx / Self::SPEED * 0.5, self.y - self.vy / Self::SPEED * 0.5);
+        draw_shadow(screen_x, screen_y, 4.0);
+        // Shaft at chest height with a pale fletching at the tail
+        let lift = 18.0;
+        draw_line(tail_x, tail_y - lift, screen_x, screen_y - lift, 2.0, Color::from_rgba(150, 110, 60, 255));
+        draw_circle(tail_x, tail_y - lift, 2.5, Color::from_rgba(230, 230, 220, 255));
+        draw_circle(screen_x, screen_y - lift, 1.5, LIGHTGRAY);
+    }
+}
+
+/// The player's weapon in flight; it hits the first monster in its way, then lands as a ground item
+pub struct ThrownWeapon {
+    pub x: f32,
+    pub y: f32,
+    vx: f32, // Tiles per second
+    vy: f32,
+    pub weapon: Weapon,
+    pub damage: i32, // Rolled when thrown, strength bonus included
+    range_left: f32, // Tiles it can still travel before dropping
+    spin: f32,       // Draw-only rotation in radians
+}
+
+impl ThrownWeapon {
+    const SPEED: f32 = 12.0;
+    const RANGE: f32 = 6.0;
+    pub const HIT_RADIUS: f32 = 0.5;
+
+    /// Advance the throw; returns false once it should land (out of range, or about to hit something solid).
+    /// It stops short of solid tiles so the weapon never lands somewhere the player can't reach.
+    pub fn update(&mut self, dt: f32, world: &World) -> bool {
+        let step = (Self::SPEED * dt).min(self.range_left);
+        let (next_x, next_y) = (self.x + self.vx / Self::SPEED * step, self.y + self.vy / Self::SPEED * step);
+        if world.is_blocked(next_x, next_y) {
+            return false;
+        }
+        self.x = next_x;
+        self.y = next_y;
+        self.range_left -= step;
+        self.spin += dt * 18.0;
+        self.range_left > 0.0
+    }
+
+    /// Whether the throw touches a body of `radius` tiles centered at (x, y)
+    pub fn hits(&self, x: f32, y: f32, radius: f32) -> bool {
+        let dx = x - self.x;
+        let dy = y - self.y;
+        (dx * dx + dy * dy).sqrt() <= Self::HIT_RADIUS + radius
+    }
+
+    /// A spinning blade at chest height over a ground shadow
+    pub fn draw(&self, camera: &GameCamera) {
+        let (screen_x, screen_y) = camera.world_to_screen(self.x, self.y);
+        draw_shadow(screen_x, screen_y, 6.0);
+        let (x, y) = (screen_x, screen_y - 18.0);
+        let (cos, sin) = (self.spin.cos() * 10.0, self.spin.sin() * 10.0);
+        draw_line(x - cos, y - sin * 0.5, x + cos, y + sin * 0.5, 3.0, LIGHTGRAY);
+        draw_circle(x + cos, y + sin * 0.5, 3.0, WHITE);
+    }
+}
+
 pub struct Player {
     pub x: f32,
     pub y: f32,
     pub health: i32,
-    pub max_health: i32,
-    pub weapon: WeaponType,
-    pub armor: Option<ArmorType>,
+    max_health: i32, // Always at least 1; set through `set_max_health`
+    pub max_mana: u32, // Pool for spells; nothing spends it yet
+    pub class: StartingClass,
+    pub weapon: Option<Weapon>, // None = fighting with fists
+    pub armor: Option<Armor>,
+    pub off_hand: Option<Armor>,
     pub inventory: Inventory,
+    pub gold: u32,
+    pub level: u32,
+    pub xp: u32, // Progress toward the next level
+    pub pending_level_ups: u32, // Stat points not yet spent
+    pub strength: u32,
+    pub vitality: u32,
+    pub dexterity: u32,
+    pub magnetism: u32,
     pub attack_cooldown: f32,
+    pub combo: u32,       // Swings chained without a long enough pause
+    pub combo_timer: f32, // Time left to swing again before the combo drops
     pub regen_timer: f32,
+    pub regen_delay_timer: f32, // Regen is paused until this reaches zero
+    pub status_effects: Vec<StatusEffect>,
+    pub dash_cooldown: f32,
+    pub invuln_timer: f32, // Hits are ignored while this is above zero
+    dash_timer: f32,            // Remaining time of the dash movement
+    dash_direction: (f32, f32), // Unit world-space direction of the current dash
+    terrain_speed: f32,         // Eased terrain multiplier so biome edges don't jolt the pace
+    poison_accumulator: f32, // Fractional poison damage not yet applied
+    recall_channel: Option<f32>, // Seconds spent channeling the town recall, if one is underway
+    pub recall_cooldown: f32,
+    broken_items: Vec<String>, // Names of gear that broke since the last drain
+    largest_hit: i32,          // Most health lost to a single hit or poison tick since the last drain
     pub facing: Direction,
 }
 
 impl Player {
+    const REGEN_DELAY: f32 = 3.0; // Seconds without taking damage before regen resumes
+    pub const BASE_SPEED: f32 = 5.0; // Tiles per second unarmored
+    pub const STRENGTH_DAMAGE: i32 = 1; // Per point
+    const BASE_CARRY: u32 = 70;
+    const STRENGTH_CARRY: u32 = 5; // Extra carry capacity per point
+    const ENCUMBERED_SPEED: f32 = 0.6; // Walking speed multiplier while over capacity
+    pub const VITALITY_HEALTH: i32 = 10; // Per point
+    pub const DEXTERITY_SPEED: f32 = 0.06; // Attack rate bonus per point
+    const BASE_PICKUP_RANGE: f32 = 0.5;
+    pub const MAGNETISM_PICKUP_RANGE: f32 = 0.25; // Per point
+    pub const MAGNETISM_PULL_RANGE: f32 = 1.0; // Per point, past the pickup range
+    const DASH_DISTANCE: f32 = 2.0;
+    const DASH_DURATION: f32 = 0.15;
+    const DASH_COOLDOWN: f32 = 1.2;
+    const DASH_INVULN: f32 = 0.2; // Barely outlasts the dash itself
+    pub const SPAWN_PROTECTION: f32 = 2.0; // I-frames after respawning
+    const RECALL_CHANNEL: f32 = 3.0; // Seconds of standing still to recall to town
+    const RECALL_COOLDOWN: f32 = 30.0;
+    const COMBO_WINDOW: f32 = 2.0;
+    const ARRIVE_DISTANCE: f32 = 0.1; // How close a walk order has to get
+    const COMBO_STEP: f32 = 0.1; // Damage bonus per chained swing after the first
+    const COMBO_MAX_BONUS: f32 = 0.5;
+    const COMBO_BREAKS_ON_HIT: bool = true; // Whether a landed monster hit drops the combo
+
+    /// A Warrior, the default loadout
     pub fn new(x: f32, y: f32) -> Self {
+        Self::with_class(x, y, StartingClass::Warrior)
+    }
+
+    pub fn with_class(x: f32, y: f32, class: StartingClass) -> Self {
         Self {
             x,
             y,
-            health: 50,
-            max_health: 50,
-            weapon: WeaponType::Sword,
-            armor: None,
+            health: class.max_health(),
+            max_health: class.max_health(),
+            class,
+            weapon: Some(Weapon::starter(class.weapon())),
+            armor: class.armor().map(Armor::starter),
+            max_mana: class.max_mana(),
+            off_hand: None,
             inventory: Inventory::new(),
+            gold: 0,
+            level: 1,
+            xp: 0,
+            pending_level_ups: 0,
+            strength: 0,
+            vitality: 0,
+            dexterity: 0,
+            magnetism: 0,
             attack_cooldown: 0.0,
+            combo: 0,
+            combo_timer: 0.0,
             regen_timer: 0.0,
+            regen_delay_timer: 0.0,
+            status_effects: Vec::new(),
+            dash_cooldown: 0.0,
+            invuln_timer: 0.0,
+            dash_timer: 0.0,
+            dash_direction: (0.0, 0.0),
+            terrain_speed: 1.0,
+            poison_accumulator: 0.0,
+            recall_channel: None,
+            recall_cooldown: 0.0,
+            broken_items: Vec::new(),
+            largest_hit: 0,
             facing: Direction::UpRight, // Default facing - weapon starts in right hand, pointing up
         }
     }
 
-    pub fn update(&mut self, dt: f32, _world: &World) {
-        // Movement (5 tiles per second)
-        let speed: f32 = 5.0;
-        let mut dx: f32 = 0.0;
-        let mut dy: f32 = 0.0;
-
-        // WASD movement - adjusted for isometric feel
-        // W/Up = move up-left in world space
-        // S/Down = move down-right in world space
-        // A/Left = move down-left in world space
-        // D/Right = move up-right in world space
-        if is_key_down(KeyCode::W) || is_key_down(KeyCode::Up) {
-            dx -= 1.0;
-            dy -= 1.0;
-        }
-        if is_key_down(KeyCode::S) || is_key_down(KeyCode::Down) {
-            dx += 1.0;
-            dy += 1.0;
-        }
-        if is_key_down(KeyCode::A) || is_key_down(KeyCode::Left) {
-            dx -= 1.0;
-            dy += 1.0;
-        }
-        if is_key_down(KeyCode::D) || is_key_down(KeyCode::Right) {
-            dx += 1.0;
-            dy -= 1.0;
-        }
+    pub fn update(&mut self, dt: f32, world: &World) {
+        // Ease toward the current tile's terrain modifier over roughly a quarter second
+        let terrain_target = world.speed_modifier_at(self.x, self.y);
+        self.terrain_speed += (terrain_target - self.terrain_speed) * (1.0 - (-8.0 * dt).exp());
+        let speed = self.move_speed() * self.terrain_speed;
+        let (mut dx, mut dy) = movement_input();
 
         // Normalize diagonal movement
         let len = (dx * dx + dy * dy).sqrt();
@@ -107,26 +450,53 @@ impl Player {
             dy /= len;
 
             // Update facing direction based on movement
-            // In isometric: dx < 0 means moving toward left side of screen
-            //               dy < 0 means moving toward top of screen
-            self.facing = match (dx < 0.0, dy < 0.0) {
-                (true, true) => Direction::UpLeft,    // Moving up-left (W)
-                (false, true) => Direction::UpRight,  // Moving up-right (D)
-                (true, false) => Direction::DownLeft, // Moving down-left (A)
-                (false, false) => Direction::DownRight, // Moving down-right (S)
-            };
+            self.facing = Direction::from_vector(dx, dy);
         }
 
-        self.x += dx * speed * dt;
-        self.y += dy * speed * dt;
+        // Any movement input breaks a recall channel
+        if len > 0.0 {
+            self.recall_channel = None;
+        }
+        self.recall_cooldown = (self.recall_cooldown - dt).max(0.0);
+        if let Some(elapsed) = &mut self.recall_channel {
+            *elapsed += dt;
+        }
+
+        // Dash toward the held direction, or straight ahead when standing still
+        if is_key_pressed(KeyCode::Space) {
+            let direction = if len > 0.0 { (dx, dy) } else { self.facing.world_vector() };
+            self.dash(direction);
+        }
+
+        self.dash_cooldown = (self.dash_cooldown - dt).max(0.0);
+        self.invuln_timer = (self.invuln_timer - dt).max(0.0);
+
+        if self.dash_timer > 0.0 {
+            // The dash replaces walking until it finishes
+            let step = dt.min(self.dash_timer);
+            self.dash_timer -= step;
+            let dash_speed = Self::DASH_DISTANCE / Self::DASH_DURATION;
+            self.move_by(self.dash_direction.0 * dash_speed * step, self.dash_direction.1 * dash_speed * step, world);
+        } else {
+            self.move_by(dx * speed * dt, dy * speed * dt, world);
+        }
 
         // Attack cooldown
         if self.attack_cooldown > 0.0 {
             self.attack_cooldown -= dt;
         }
 
-        // Health regeneration (1 HP per second)
-        if self.health < self.max_health {
+        self.combo_timer = (self.combo_timer - dt).max(0.0);
+        if self.combo_timer <= 0.0 {
+            self.combo = 0;
+        }
+
+        self.update_status_effects(dt);
+
+        // Health regeneration (1 HP per second), paused for a while after each hit and while poisoned
+        if self.regen_delay_timer > 0.0 {
+            self.regen_delay_timer = (self.regen_delay_timer - dt).max(0.0);
+        } else if self.health < self.max_health && !self.is_poisoned() {
             self.regen_timer += dt;
             if self.regen_timer >= 1.0 {
                 self.regen_timer -= 1.0;
@@ -135,51 +505,546 @@ impl Player {
         }
     }
 
+    fn update_status_effects(&mut self, dt: f32) {
+        for effect in &mut self.status_effects {
+            match effect {
+                StatusEffect::Poison { dps, remaining } => {
+                    self.poison_accumulator += *dps * dt.min(*remaining);
+                    *remaining -= dt;
+                }
+            }
+        }
+        self.status_effects.retain(|e| !e.is_expired());
+
+        // Poison bypasses armor and the regen delay, and never deals the killing blow
+        let whole = self.poison_accumulator.floor();
+        if whole >= 1.0 {
+            self.poison_accumulator -= whole;
+            let before = self.health;
+            self.health = (self.health - whole as i32).max(1);
+            self.largest_hit = self.largest_hit.max(before - self.health);
+        }
+        if !self.is_poisoned() {
+            self.poison_accumulator = 0.0;
+        }
+    }
+
+    pub fn is_poisoned(&self) -> bool {
+        self.status_effects
+            .iter()
+            .any(|e| matches!(e, StatusEffect::Poison { .. }))
+    }
+
+    /// Apply a status effect. Re-applying poison refreshes the existing stack
+    /// (strongest dps, longest duration) instead of adding another one.
+    pub fn apply_status(&mut self, effect: StatusEffect) {
+        match effect {
+            StatusEffect::Poison { dps, remaining } => {
+                let existing = self
+                    .status_effects
+                    .iter_mut()
+                    .find(|e| matches!(e, StatusEffect::Poison { .. }));
+                if let Some(StatusEffect::Poison { dps: old_dps, remaining: old_remaining }) = existing {
+                    *old_dps = old_dps.max(dps);
+                    *old_remaining = old_remaining.max(remaining);
+                } else {
+                    self.status_effects.push(effect);
+                }
+            }
+        }
+    }
+
+    /// Hurl the equipped weapon toward a world point, leaving the player on fists until it's
+    /// picked back up. Uses up the swing, and rolls damage with the weapon still in hand.
+    pub fn throw_weapon(&mut self, target_x: f32, target_y: f32, rng: &mut GameRng) -> Option<ThrownWeapon> {
+        let (dx, dy) = (target_x - self.x, target_y - self.y);
+        let len = (dx * dx + dy * dy).sqrt();
+        if !self.can_attack() || self.weapon.is_none() || len <= f32::EPSILON {
+            return None;
+        }
+
+        self.attack_cooldown = self.weapon_cooldown();
+        self.extend_combo();
+        let damage = self.calculate_damage(rng);
+        Some(ThrownWeapon {
+            x: self.x,
+            y: self.y,
+            vx: dx / len * ThrownWeapon::SPEED,
+            vy: dy / len * ThrownWeapon::SPEED,
+            weapon: self.weapon.take()?,
+            damage,
+            range_left: ThrownWeapon::RANGE,
+            spin: 0.0,
+        })
+    }
+
     pub fn can_attack(&self) -> bool {
         self.attack_cooldown <= 0.0
     }
 
-    pub fn attack(&mut self) {
-        self.attack_cooldown = 0.3; // 0.3 second cooldown
+    /// Use the weapon toward a world point. A bow turns to face it and returns an arrow; a melee swing
+    /// only starts the cooldown here and the caller resolves what the arc hits.
+    pub fn attack(&mut self, target_x: f32, target_y: f32, rng: &mut GameRng) -> Option<Arrow> {
+        self.attack_cooldown = self.weapon_cooldown();
+        self.extend_combo();
+
+        // Damage is rolled before the shot wears the bow, in case this is the one that breaks it
+        let arrow = if self.has_ranged_weapon() {
+            let (dx, dy) = (target_x - self.x, target_y - self.y);
+            let len = (dx * dx + dy * dy).sqrt();
+            let direction = if len > f32::EPSILON { (dx / len, dy / len) } else { self.facing.world_vector() };
+            self.facing = Direction::from_vector(direction.0, direction.1);
+            Some(Arrow::new(self.x, self.y, direction, self.calculate_damage(rng)))
+        } else {
+            None
+        };
+
+        // Every swing wears the weapon; a broken weapon leaves us with fists
+        if let Some(weapon) = &mut self.weapon
+            && weapon.wear()
+        {
+            self.broken_items.push(weapon.name().to_string());
+            self.weapon = None;
+        }
+        arrow
+    }
+
+    /// Count a swing toward the combo and restart its window
+    fn extend_combo(&mut self) {
+        self.combo += 1;
+        self.combo_timer = Self::COMBO_WINDOW;
+    }
+
+    /// Damage multiplier from the current combo; the first swing of a chain gets no bonus
+    pub fn combo_multiplier(&self) -> f32 {
+        1.0 + (self.combo.saturating_sub(1) as f32 * Self::COMBO_STEP).min(Self::COMBO_MAX_BONUS)
+    }
+
+    /// Seconds between swings for whatever is in hand
+    pub fn weapon_cooldown(&self) -> f32 {
+        let base = self.weapon.as_ref().map(|w| w.kind.attack_cooldown()).unwrap_or(FIST_COOLDOWN);
+        base / (1.0 + self.dexterity as f32 * Self::DEXTERITY_SPEED)
+    }
+
+    pub fn has_ranged_weapon(&self) -> bool {
+        self.weapon.as_ref().is_some_and(|w| w.kind.is_ranged())
+    }
+
+    /// How far away an attack can land: arrow range with a bow, melee reach otherwise
+    pub fn attack_reach(&self) -> f32 {
+        if self.has_ranged_weapon() { Arrow::RANGE } else { ATTACK_RANGE }
+    }
+
+    /// Whether a swing hits every monster in the arc rather than just one (fists never cleave)
+    pub fn cleaves(&self) -> bool {
+        self.weapon.as_ref().is_some_and(|w| w.kind.is_cleave())
+    }
+
+    pub fn stun_chance(&self) -> f32 {
+        self.weapon.as_ref().map_or(0.0, |w| w.kind.stun_chance())
+    }
+
+    /// How far the current swing cooldown has recovered: 0.0 just swung, 1.0 ready
+    pub fn attack_readiness(&self) -> f32 {
+        (1.0 - self.attack_cooldown.max(0.0) / self.weapon_cooldown()).clamp(0.0, 1.0)
+    }
+
+    /// Inclusive (min, max) damage for whatever is in hand
+    pub fn damage_range(&self) -> (i32, i32) {
+        let (min, max) = self.weapon.as_ref().map(|w| w.damage_range()).unwrap_or(FIST_DAMAGE);
+        let bonus = self.strength_bonus();
+        (min + bonus, max + bonus)
+    }
+
+    /// How close loot has to be for auto-pickup (and walking over a thrown weapon) to take it
+    pub fn pickup_range(&self) -> f32 {
+        Self::BASE_PICKUP_RANGE + self.magnetism as f32 * Self::MAGNETISM_PICKUP_RANGE
+    }
+
+    /// Loot within this range drifts toward the player; no further than the pickup range without Magnetism
+    pub fn magnet_range(&self) -> f32 {
+        self.pickup_range() + self.magnetism as f32 * Self::MAGNETISM_PULL_RANGE
+    }
+
+    fn strength_bonus(&self) -> i32 {
+        self.strength as i32 * Self::STRENGTH_DAMAGE
+    }
+
+    /// XP needed to go from the current level to the next
+    pub fn xp_to_next_level(&self) -> u32 {
+        self.level * 25
+    }
+
+    /// Add XP, queueing a stat choice (and a full heal) for every level gained
+    pub fn gain_xp(&mut self, amount: u32) {
+        self.xp += amount;
+        while self.xp >= self.xp_to_next_level() {
+            self.xp -= self.xp_to_next_level();
+            self.level += 1;
+            self.pending_level_ups += 1;
+            self.health = self.max_health;
+        }
+    }
+
+    /// Spend one pending level-up on a stat
+    pub fn apply_stat(&mut self, stat: Stat) {
+        if self.pending_level_ups == 0 {
+            return;
+        }
+        self.pending_level_ups -= 1;
+        match stat {
+            Stat::Strength => self.strength += 1,
+            Stat::Vitality => {
+                self.vitality += 1;
+                self.set_max_health(self.max_health + Self::VITALITY_HEALTH);
+                self.health += Self::VITALITY_HEALTH;
+            }
+            Stat::Dexterity => self.dexterity += 1,
+            Stat::Magnetism => self.magnetism += 1,
+        }
+    }
+
+    /// Names of equipment that broke since the last call
+    pub fn take_broken_items(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.broken_items)
+    }
+
+    /// Biggest single loss of health since the last call, from any source
+    pub fn take_largest_hit(&mut self) -> i32 {
+        std::mem::take(&mut self.largest_hit)
+    }
+
+    /// True during the first third of the cooldown, when the swing is drawn
+    pub fn is_swinging(&self) -> bool {
+        self.attack_cooldown > self.weapon_cooldown() * (2.0 / 3.0)
+    }
+
+    /// Whether a body of `radius` tiles centered at a world position overlaps the swing arc
+    pub fn in_attack_cone(&self, x: f32, y: f32, radius: f32) -> bool {
+        let dx = x - self.x;
+        let dy = y - self.y;
+        let dist = (dx * dx + dy * dy).sqrt();
+        if dist - radius > ATTACK_RANGE {
+            return false;
+        }
+        if dist <= radius {
+            return true; // Player is inside the body (or exactly on a point target) - no direction, always hit
+        }
+        let (fx, fy) = self.facing.world_vector();
+        // Widen the arc by the angle the body covers, so its edge counts, not just its center
+        angle_between(fx, fy, dx, dy) <= ATTACK_CONE_HALF_ANGLE + (radius / dist).asin()
+    }
+
+    pub fn calculate_damage(&self, rng: &mut GameRng) -> i32 {
+        let base = match &self.weapon {
+            Some(weapon) => weapon.roll_damage(rng),
+            None => rng.gen_range(FIST_DAMAGE.0, FIST_DAMAGE.1 + 1),
+        };
+        ((base + self.strength_bonus()) as f32 * self.combo_multiplier()).round() as i32
+    }
+
+    pub fn block_chance(&self) -> f32 {
+        self.off_hand.as_ref().map(|a| a.kind.block_chance()).unwrap_or(0.0)
+    }
+
+    pub fn damage_reduction(&self) -> i32 {
+        self.damage_reduction_for(DamageType::Physical)
+    }
+
+    pub fn damage_reduction_for(&self, dtype: DamageType) -> i32 {
+        self.armor.as_ref().map(|a| a.reduction_for(dtype)).unwrap_or(0)
+    }
+
+    /// Wear an armor slot by one hit, emptying it if the piece breaks
+    fn wear_armor(slot: &mut Option<Armor>, broken_items: &mut Vec<String>) {
+        if let Some(armor) = slot
+            && armor.wear()
+        {
+            broken_items.push(armor.name().to_string());
+            *slot = None;
+        }
+    }
+
+    /// Walking speed after the armor and off-hand speed modifiers and any encumbrance.
+    /// Terrain is applied on top of this in `update`.
+    pub fn move_speed(&self) -> f32 {
+        let modifier = |slot: &Option<Armor>| slot.as_ref().map_or(1.0, |a| a.kind.speed_modifier());
+        let encumbrance = if self.is_encumbered() { Self::ENCUMBERED_SPEED } else { 1.0 };
+        Self::BASE_SPEED * modifier(&self.armor) * modifier(&self.off_hand) * encumbrance
+    }
+
+    /// Total weight of worn gear and the backpack, summed fresh so it's always current
+    pub fn carried_weight(&self) -> u32 {
+        let equipped = [
+            self.weapon.clone().map(Item::Weapon),
+            self.armor.clone().map(Item::Armor),
+            self.off_hand.clone().map(Item::Armor),
+        ];
+        let worn: u32 = equipped.iter().flatten().map(Item::weight).sum();
+        worn + self.inventory.items.iter().map(Item::weight).sum::<u32>()
+    }
+
+    pub fn carry_capacity(&self) -> u32 {
+        Self::BASE_CARRY + self.strength * Self::STRENGTH_CARRY
+    }
+
+    pub fn is_encumbered(&self) -> bool {
+        self.carried_weight() > self.carry_capacity()
+    }
+
+    /// Start a dash in `dir` if it's off cooldown
+    pub fn dash(&mut self, dir: (f32, f32)) {
+        let len = (dir.0 * dir.0 + dir.1 * dir.1).sqrt();
+        if self.dash_cooldown > 0.0 || len == 0.0 {
+            return;
+        }
+        self.dash_direction = (dir.0 / len, dir.1 / len);
+        self.recall_channel = None;
+        self.dash_timer = Self::DASH_DURATION;
+        self.dash_cooldown = Self::DASH_COOLDOWN;
+        self.invuln_timer = Self::DASH_INVULN;
+    }
+
+    /// Put the player back on their feet at `(x, y)`: full health, no lingering effects, brief i-frames
+    pub fn respawn_at(&mut self, x: f32, y: f32) {
+        self.x = x;
+        self.y = y;
+        self.health = self.max_health;
+        self.status_effects.clear();
+        self.poison_accumulator = 0.0;
+        self.dash_timer = 0.0;
+        self.attack_cooldown = 0.0;
+        self.combo = 0;
+        self.recall_channel = None;
+        self.invuln_timer = Self::SPAWN_PROTECTION;
+    }
+
+    /// Begin channeling the recall to town; ignored while it's cooling down or already underway
+    pub fn start_recall(&mut self) {
+        if self.recall_cooldown <= 0.0 && self.recall_channel.is_none() {
+            self.recall_channel = Some(0.0);
+        }
+    }
+
+    /// 0.0 to 1.0 through the recall channel, if one is underway
+    pub fn recall_progress(&self) -> Option<f32> {
+        self.recall_channel.map(|elapsed| (elapsed / Self::RECALL_CHANNEL).min(1.0))
+    }
+
+    /// True once, on the frame the channel completes; the cooldown starts then
+    pub fn take_finished_recall(&mut self) -> bool {
+        if self.recall_progress().is_some_and(|p| p >= 1.0) {
+            self.recall_channel = None;
+            self.recall_cooldown = Self::RECALL_COOLDOWN;
+            return true;
+        }
+        false
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invuln_timer > 0.0
+    }
+
+    /// Walk a frame's worth toward a world point, as if steered there by the keys (breaks a recall
+    /// channel, faces the way it goes). Returns true once the point is reached.
+    pub fn walk_toward(&mut self, x: f32, y: f32, dt: f32, world: &World) -> bool {
+        let (dx, dy) = (x - self.x, y - self.y);
+        let dist = (dx * dx + dy * dy).sqrt();
+        let step = self.move_speed() * self.terrain_speed * dt;
+        if dist <= step.max(Self::ARRIVE_DISTANCE) {
+            return true;
+        }
+        if self.dash_timer > 0.0 {
+            return false; // The dash carries us this frame
+        }
+        self.facing = Direction::from_vector(dx, dy);
+        self.recall_channel = None;
+        self.move_by(dx / dist * step, dy / dist * step, world);
+        false
+    }
+
+    /// Move with collision against solid decorations, sliding along whichever axis is free
+    fn move_by(&mut self, dx: f32, dy: f32, world: &World) {
+        // Sub-step so a fast dash on a slow frame can't tunnel through a tile
+        let steps = ((dx.abs().max(dy.abs()) / 0.25).ceil() as i32).max(1);
+        let (step_x, step_y) = (dx / steps as f32, dy / steps as f32);
+
+        for _ in 0..steps {
+            if !world.is_blocked(self.x + step_x, self.y) {
+                self.x += step_x;
+            }
+            if !world.is_blocked(self.x, self.y + step_y) {
+                self.y += step_y;
+            }
+        }
+    }
+
+    pub fn max_health(&self) -> i32 {
+        self.max_health
+    }
+
+    /// Change max health, never below 1 so health fractions can't divide by zero; health is trimmed to fit
+    pub fn set_max_health(&mut self, value: i32) {
+        self.max_health = value.max(1);
+        self.health = self.health.min(self.max_health);
+    }
+
+    /// Health as a fraction of max, clamped to 0..=1
+    pub fn health_fraction(&self) -> f32 {
+        (self.health as f32 / self.max_health.max(1) as f32).clamp(0.0, 1.0)
     }
 
-    pub fn calculate_damage(&self) -> i32 {
-        self.weapon.roll_damage()
+    pub fn heal(&mut self, amount: i32) {
+        self.health = (self.health + amount).min(self.max_health);
     }
 
-    pub fn take_damage(&mut self, raw_damage: i32) {
-        let reduction = self.armor.as_ref().map(|a| a.damage_reduction()).unwrap_or(0);
+    /// Apply a hit and return the damage actually dealt (0 if blocked or dodged).
+    /// Body armor reduces it according to `dtype`; the block roll comes from the run's `rng`.
+    pub fn take_damage(&mut self, raw_damage: i32, dtype: DamageType, rng: &mut GameRng) -> i32 {
+        // Dash i-frames ignore the hit entirely
+        if self.is_invulnerable() {
+            return 0;
+        }
+
+        // Shield block is rolled before armor and negates the whole hit
+        if rng.gen_f32() < self.block_chance() {
+            Self::wear_armor(&mut self.off_hand, &mut self.broken_items);
+            return 0;
+        }
+
+        // A landed hit breaks a recall channel (poison ticks don't, or a poisoned player could never get home)
+        self.recall_channel = None;
+        if Self::COMBO_BREAKS_ON_HIT {
+            self.combo = 0;
+        }
+
+        let reduction = self.damage_reduction_for(dtype);
+        Self::wear_armor(&mut self.armor, &mut self.broken_items);
         // Minimum damage is always 1 - armor can never reduce damage to zero
         let damage = (raw_damage - reduction).max(1);
         self.health = (self.health - damage).max(0);
+        self.largest_hit = self.largest_hit.max(damage);
+
+        // Restart the regen delay on every hit that lands
+        self.regen_delay_timer = Self::REGEN_DELAY;
+        self.regen_timer = 0.0;
+
+        // Nothing lingers past death
+        if self.health <= 0 {
+            self.status_effects.clear();
+        }
+
+        damage
     }
 
-    pub fn equip_item(&mut self, item: Item) -> Option<Item> {
+    /// The currently equipped item occupying the slot `item` would go into
+    pub fn equipped_for(&self, item: &Item) -> Option<Item> {
+        match item {
+            Item::Weapon(_) => self.weapon.clone().map(Item::Weapon),
+            Item::Armor(a) if a.kind.is_off_hand() => self.off_hand.clone().map(Item::Armor),
+            Item::Armor(_) => self.armor.clone().map(Item::Armor),
+        }
+    }
+
+    /// Name of the equipped item that would be forced off by a hand conflict when equipping `item`
+    pub fn forced_unequip(&self, item: &Item) -> Option<&str> {
+        match item {
+            Item::Weapon(w) if w.kind.is_two_handed() => self.off_hand.as_ref().map(|a| a.name()),
+            Item::Armor(a) if a.kind.is_off_hand() => {
+                self.weapon.as_ref().filter(|w| w.kind.is_two_handed()).map(|w| w.name())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn meets_requirement(&self, item: &Item) -> bool {
+        self.level >= item.required_level()
+    }
+
+    /// Equip an item, returning everything it displaced (the old item in that slot, plus
+    /// a shield or two-hander that can't share the player's hands with it).
+    /// An item above the player's level is handed back untouched as the error.
+    pub fn equip_item(&mut self, item: Item) -> Result<Vec<Item>, Item> {
+        if !self.meets_requirement(&item) {
+            return Err(item);
+        }
+        let mut displaced = Vec::new();
         match item {
             Item::Weapon(w) => {
-                let old = Item::Weapon(self.weapon.clone());
-                self.weapon = w;
-                Some(old)
+                if w.kind.is_two_handed() {
+                    displaced.extend(self.off_hand.take().map(Item::Armor));
+                }
+                displaced.extend(self.weapon.replace(w).map(Item::Weapon));
             }
-            Item::Armor(a) => {
-                let old = self.armor.take().map(Item::Armor);
-                self.armor = Some(a);
-                old
+            Item::Armor(a) if a.kind.is_off_hand() => {
+                if self.weapon.as_ref().is_some_and(|w| w.kind.is_two_handed()) {
+                    displaced.extend(self.weapon.take().map(Item::Weapon));
+                }
+                displaced.extend(self.off_hand.replace(a).map(Item::Armor));
             }
+            Item::Armor(a) => displaced.extend(self.armor.replace(a).map(Item::Armor)),
         }
+        Ok(displaced)
+    }
+
+    /// Move the equipped weapon into the backpack, leaving the player on fists.
+    /// False when the backpack is full or nothing is equipped; nothing moves then.
+    pub fn unequip_weapon(&mut self) -> bool {
+        Self::unequip_into(&mut self.weapon, &mut self.inventory, Item::Weapon)
+    }
+
+    /// Body armor counterpart of `unequip_weapon`
+    pub fn unequip_armor(&mut self) -> bool {
+        Self::unequip_into(&mut self.armor, &mut self.inventory, Item::Armor)
+    }
+
+    /// Off-hand counterpart of `unequip_weapon`
+    pub fn unequip_off_hand(&mut self) -> bool {
+        Self::unequip_into(&mut self.off_hand, &mut self.inventory, Item::Armor)
+    }
+
+    fn unequip_into<T>(slot: &mut Option<T>, inventory: &mut Inventory, to_item: fn(T) -> Item) -> bool {
+        if inventory.is_full() {
+            return false;
+        }
+        slot.take().is_some_and(|equipped| inventory.add_item(to_item(equipped)))
     }
 
     pub fn draw(&self, camera: &GameCamera) {
         let (screen_x, screen_y) = camera.world_to_screen(self.x, self.y);
 
+        draw_shadow(screen_x, screen_y, 16.0);
+
+        // Recall channel: a ring on the ground filling up clockwise
+        if let Some(progress) = self.recall_progress() {
+            draw_ellipse_lines(screen_x, screen_y, 26.0, 13.0, 0.0, 1.5, Color::new(0.4, 0.7, 1.0, 0.35));
+            let segments = (progress * 32.0).ceil() as usize;
+            for i in 0..segments {
+                let (a, b) = (i as f32 / 32.0, ((i + 1) as f32 / 32.0).min(progress));
+                let point = |t: f32| {
+                    let angle = t * std::f32::consts::TAU - std::f32::consts::FRAC_PI_2;
+                    (screen_x + angle.cos() * 26.0, screen_y + angle.sin() * 13.0)
+                };
+                let ((x1, y1), (x2, y2)) = (point(a), point(b));
+                draw_line(x1, y1, x2, y2, 3.0, Color::new(0.5, 0.8, 1.0, 0.9));
+            }
+        }
+
+        // Swing arc sits on the ground beneath the body (bows don't sweep one)
+        if self.is_swinging() && !self.has_ranged_weapon() {
+            self.draw_swing_arc(camera);
+        }
+
         // Draw player as a simple shape
         // Base body color depends on armor
-        let body_color = match &self.armor {
-            None => Color::from_rgba(200, 150, 100, 255), // Skin tone - no armor
+        let body_color = match self.armor.as_ref().map(|a| a.kind) {
+            None | Some(ArmorType::Shield) => Color::from_rgba(200, 150, 100, 255), // Skin tone - no armor
             Some(ArmorType::Leather) => Color::from_rgba(139, 90, 43, 255), // Brown
             Some(ArmorType::Chainmail) => Color::from_rgba(150, 150, 160, 255), // Silver
             Some(ArmorType::Platemail) => Color::from_rgba(100, 100, 120, 255), // Dark steel
         };
+        // Ghostly while dash i-frames are active
+        let body_color = if self.is_invulnerable() { Color { a: 0.5, ..body_color } } else { body_color };
 
         // Body (diamond shape for isometric)
         draw_poly(screen_x, screen_y - 10.0, 4, 20.0, 45.0, body_color);
@@ -188,33 +1053,208 @@ impl Player {
         draw_circle(screen_x, screen_y - 35.0, 10.0, Color::from_rgba(220, 180, 140, 255));
 
         // Weapon indicator (line extending from body in facing direction)
-        let weapon_color = match &self.weapon {
-            WeaponType::Sword => LIGHTGRAY,
-            WeaponType::Axe => Color::from_rgba(100, 80, 60, 255),
-            WeaponType::Mace => DARKGRAY,
+        // Bare-handed players draw no weapon
+        if let Some(weapon) = &self.weapon {
+            let weapon_color = match weapon.kind {
+                WeaponType::Sword => LIGHTGRAY,
+                WeaponType::Axe => Color::from_rgba(100, 80, 60, 255),
+                WeaponType::Mace => DARKGRAY,
+                WeaponType::Bow => Color::from_rgba(150, 100, 50, 255),
+                WeaponType::Staff => Color::from_rgba(120, 90, 160, 255),
+            };
+
+            let (weapon_start_x, weapon_start_y) = self.facing.weapon_offset();
+            let (weapon_end_x, weapon_end_y) = self.facing.weapon_end_offset();
+            let start = Vec2::new(screen_x + weapon_start_x, screen_y + weapon_start_y);
+            let end = Vec2::new(screen_x + weapon_end_x, screen_y + weapon_end_y);
+
+            if weapon.kind.is_ranged() {
+                // Limbs bowed out sideways from the string, which runs straight between the tips
+                let bulge = if weapon_start_x < 0.0 { -6.0 } else { 6.0 };
+                let grip = (start + end) / 2.0 + Vec2::new(bulge, 0.0);
+                draw_line(start.x, start.y, grip.x, grip.y, 2.5, weapon_color);
+                draw_line(grip.x, grip.y, end.x, end.y, 2.5, weapon_color);
+                draw_line(start.x, start.y, end.x, end.y, 1.0, LIGHTGRAY);
+            } else {
+                draw_line(start.x, start.y, end.x, end.y, 3.0, weapon_color);
+            }
+        }
+
+        // Shield held in the hand opposite the weapon
+        if self.off_hand.is_some() {
+            let (weapon_x, _) = self.facing.weapon_offset();
+            draw_poly(
+                screen_x - weapon_x,
+                screen_y - 12.0,
+                6,
+                9.0,
+                0.0,
+                Color::from_rgba(120, 90, 50, 255),
+            );
+            draw_poly_lines(screen_x - weapon_x, screen_y - 12.0, 6, 9.0, 0.0, 1.5, LIGHTGRAY);
+        }
+
+        if let Some(progress) = self.swing_progress() {
+            self.draw_attack_animation(camera, progress);
+        }
+    }
+
+    /// Ground ring at attack reach; a circle in world space is an ellipse on the isometric screen
+    pub fn draw_attack_range(&self, camera: &GameCamera) {
+        let (x, y) = camera.world_to_screen(self.x, self.y);
+        let reach = self.attack_reach();
+        let rx = reach * TILE_WIDTH / 2.0 * std::f32::consts::SQRT_2;
+        let ry = reach * TILE_HEIGHT / 2.0 * std::f32::consts::SQRT_2;
+        draw_ellipse(x, y, rx, ry, 0.0, Color::new(1.0, 0.9, 0.6, 0.12));
+        draw_ellipse_lines(x, y, rx, ry, 0.0, 1.5, Color::new(1.0, 0.9, 0.6, 0.45));
+    }
+
+    /// 0.0 to 1.0 through the swing window (the first third of this weapon's cooldown)
+    fn swing_progress(&self) -> Option<f32> {
+        if !self.is_swinging() {
+            return None;
+        }
+        let window = self.weapon_cooldown() / 3.0;
+        let elapsed = self.weapon_cooldown() - self.attack_cooldown;
+        Some((elapsed / window).clamp(0.0, 1.0))
+    }
+
+    /// Weapon-specific strike aimed along `facing`: the sword sweeps across the arc, the axe
+    /// chops down from overhead, the mace slams into the ground and the staff thrusts forward. Fists just flash.
+    fn draw_attack_animation(&self, camera: &GameCamera, progress: f32) {
+        let (screen_x, screen_y) = camera.world_to_screen(self.x, self.y);
+        let (fx, fy) = self.facing.world_vector();
+        let facing_angle = fy.atan2(fx);
+        let hand_y = screen_y - 20.0;
+        // Ground point `reach` tiles out at `angle`, lifted to weapon height
+        let strike_point = |angle: f32, reach: f32, lift: f32| {
+            let (x, y) = camera.world_to_screen(self.x + angle.cos() * reach, self.y + angle.sin() * reach);
+            Vec2::new(x, y - lift)
         };
 
-        let (weapon_start_x, weapon_start_y) = self.facing.weapon_offset();
-        let (weapon_end_x, weapon_end_y) = self.facing.weapon_end_offset();
-
-        draw_line(
-            screen_x + weapon_start_x,
-            screen_y + weapon_start_y,
-            screen_x + weapon_end_x,
-            screen_y + weapon_end_y,
-            3.0,
-            weapon_color,
-        );
-
-        // Attack animation (flash when attacking)
-        if self.attack_cooldown > 0.2 {
-            let (flash_x, flash_y) = self.facing.attack_flash_offset();
-            draw_circle(
-                screen_x + flash_x,
-                screen_y + flash_y,
-                8.0,
-                Color::from_rgba(255, 255, 200, 150),
+        match self.weapon.as_ref().map(|w| w.kind) {
+            Some(WeaponType::Sword) => {
+                // Blade sweeps from one edge of the arc to the other, leaving a fading trail
+                let sweep = |t: f32| facing_angle - ATTACK_CONE_HALF_ANGLE + 2.0 * ATTACK_CONE_HALF_ANGLE * t;
+                for i in 0..4 {
+                    let t = (progress - i as f32 * 0.08).max(0.0);
+                    let tip = strike_point(sweep(t), ATTACK_RANGE, 14.0);
+                    let alpha = 0.9 - i as f32 * 0.22;
+                    draw_line(screen_x, hand_y, tip.x, tip.y, 3.0 - i as f32 * 0.5, Color::new(0.9, 0.9, 1.0, alpha));
+                }
+            }
+            Some(WeaponType::Axe) => {
+                // Raised overhead, then brought down hard onto the target (ease-in)
+                let fall = progress * progress;
+                let top = Vec2::new(screen_x, screen_y - 70.0);
+                let impact = strike_point(facing_angle, ATTACK_RANGE * 0.8, 4.0);
+                let head = top.lerp(impact, fall);
+                draw_line(screen_x, hand_y, head.x, head.y, 4.0, Color::from_rgba(100, 80, 60, 255));
+                draw_poly(head.x, head.y, 3, 9.0, facing_angle.to_degrees(), LIGHTGRAY);
+                if progress > 0.85 {
+                    draw_circle(impact.x, impact.y + 4.0, 10.0, Color::from_rgba(255, 255, 200, 120));
+                }
+            }
+            Some(WeaponType::Mace) => {
+                // Short wind-up, then a slam that sends a shockwave across the ground
+                let impact = strike_point(facing_angle, ATTACK_RANGE * 0.7, 0.0);
+                if progress < 0.5 {
+                    let lift = 30.0 * (1.0 - progress * 2.0);
+                    let head = Vec2::new(impact.x, impact.y - 10.0 - lift);
+                    draw_line(screen_x, hand_y, head.x, head.y, 3.0, DARKGRAY);
+                    draw_circle(head.x, head.y, 6.0, GRAY);
+                } else {
+                    let wave = (progress - 0.5) * 2.0;
+                    draw_line(screen_x, hand_y, impact.x, impact.y - 10.0, 3.0, DARKGRAY);
+                    draw_circle(impact.x, impact.y - 10.0, 6.0, GRAY);
+                    let alpha = 0.6 * (1.0 - wave);
+                    draw_ellipse_lines(impact.x, impact.y, 8.0 + wave * 22.0, 4.0 + wave * 11.0, 0.0, 2.0, Color::new(1.0, 1.0, 0.8, alpha));
+                }
+            }
+            Some(WeaponType::Staff) => {
+                // Thrust out along the facing, the tip glowing brighter as it lands
+                let reach = ATTACK_RANGE * (0.4 + 0.5 * progress);
+                let tip = strike_point(facing_angle, reach, 16.0);
+                draw_line(screen_x, hand_y, tip.x, tip.y, 3.0, Color::from_rgba(120, 90, 160, 255));
+                draw_circle(tip.x, tip.y, 4.0 + progress * 4.0, Color::new(0.7, 0.6, 1.0, 0.4 + 0.5 * progress));
+            }
+            Some(WeaponType::Bow) => {
+                // Release: a puff at the bow that fades as the string settles
+                let (offset_x, offset_y) = self.facing.weapon_offset();
+                let alpha = 0.6 * (1.0 - progress);
+                draw_circle(screen_x + offset_x, screen_y + offset_y + 8.0, 5.0 + progress * 6.0, Color::new(1.0, 1.0, 0.85, alpha));
+            }
+            None => {
+                let (flash_x, flash_y) = self.facing.attack_flash_offset();
+                draw_circle(screen_x + flash_x, screen_y + flash_y, 8.0, Color::from_rgba(255, 255, 200, 150));
+            }
+        }
+    }
+
+    /// Translucent ground wedge covering the area the swing hits, projected into isometric space
+    fn draw_swing_arc(&self, camera: &GameCamera) {
+        let (fx, fy) = self.facing.world_vector();
+        let facing_angle = fy.atan2(fx);
+        let (center_x, center_y) = camera.world_to_screen(self.x, self.y);
+        let center = Vec2::new(center_x, center_y);
+        let color = Color::from_rgba(255, 255, 200, 60);
+
+        let segments = 8;
+        let mut prev: Option<Vec2> = None;
+        for i in 0..=segments {
+            let angle = facing_angle - ATTACK_CONE_HALF_ANGLE
+                + 2.0 * ATTACK_CONE_HALF_ANGLE * i as f32 / segments as f32;
+            let (edge_x, edge_y) = camera.world_to_screen(
+                self.x + angle.cos() * ATTACK_RANGE,
+                self.y + angle.sin() * ATTACK_RANGE,
             );
+            let edge = Vec2::new(edge_x, edge_y);
+            if let Some(prev) = prev {
+                draw_triangle(center, prev, edge, color);
+            }
+            prev = Some(edge);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_health_never_drops_below_one() {
+        let mut player = Player::new(0.0, 0.0);
+        player.set_max_health(0);
+        assert!(player.max_health() >= 1);
+        player.set_max_health(-5);
+        assert!(player.max_health() >= 1);
+        assert!(player.health <= player.max_health());
+    }
+
+    #[test]
+    fn health_fraction_is_finite_and_clamped() {
+        let mut player = Player::new(0.0, 0.0);
+        player.set_max_health(0);
+        let fraction = player.health_fraction();
+        assert!(fraction.is_finite() && (0.0..=1.0).contains(&fraction));
+    }
+
+    #[test]
+    fn starting_kit_can_be_put_back_on_at_level_one() {
+        let mut player = Player::new(0.0, 0.0);
+        assert!(player.unequip_weapon() && player.unequip_armor());
+        let kit: Vec<Item> = player.inventory.items.drain(..).collect();
+        for item in kit {
+            assert!(player.equip_item(item).is_ok());
+        }
+        assert!(!player.meets_requirement(&Item::Armor(Armor::new(ArmorType::Platemail))));
+    }
+
+    #[test]
+    fn only_the_mage_starts_with_mana() {
+        let mage = Player::with_class(0.0, 0.0, StartingClass::Mage);
+        assert_eq!(mage.weapon.as_ref().map(|w| w.kind), Some(WeaponType::Staff));
+        assert!(mage.armor.is_none() && mage.max_mana > 0);
+        assert_eq!(Player::new(0.0, 0.0).max_mana, 0);
+    }
+}
@@ -0,0 +1,50 @@
+use crate::monsters::MonsterType;
+use crate::rng::GameRng;
+
+/// A kill-count objective; finishing it pays out and the next one is issued straight away
+#[derive(Clone, Debug)]
+pub struct Quest {
+    pub target: MonsterType,
+    pub required: u32,
+    pub progress: u32,
+}
+
+impl Quest {
+    const MIN_KILLS: u32 = 5;
+    const MAX_KILLS: u32 = 12;
+    const GOLD_PER_KILL: u32 = 4;
+
+    pub fn random(rng: &mut GameRng) -> Self {
+        let target = MonsterType::ALL[rng.gen_range(0, MonsterType::ALL.len() as i32) as usize];
+        // Tough types ask for fewer kills
+        let max_kills = (Self::MAX_KILLS as i32 * 10 / target.max_health()).clamp(Self::MIN_KILLS as i32, Self::MAX_KILLS as i32);
+        let required = rng.gen_range(Self::MIN_KILLS as i32, max_kills + 1) as u32;
+        Self { target, required, progress: 0 }
+    }
+
+    /// Count a kill; true when this one finishes the quest
+    pub fn record_kill(&mut self, monster_type: MonsterType) -> bool {
+        if monster_type != self.target || self.is_complete() {
+            return false;
+        }
+        self.progress += 1;
+        self.is_complete()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.progress >= self.required
+    }
+
+    pub fn description(&self) -> String {
+        format!("Kill {} {}s", self.required, self.target.name())
+    }
+
+    /// Rewards scale with how much killing was asked for
+    pub fn gold_reward(&self) -> u32 {
+        self.required * (Self::GOLD_PER_KILL + self.target.max_health() as u32 / 5)
+    }
+
+    pub fn xp_reward(&self) -> u32 {
+        self.required * (self.target.max_health() + self.target.base_damage()) as u32
+    }
+}
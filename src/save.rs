@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::combat::{Armor, ArmorType, Item, UniqueDef, UniqueStats, Weapon, WeaponType};
+use crate::difficulty::Difficulty;
+use crate::inventory::Corpse;
+use crate::monsters::MonsterType;
+use crate::player::{Player, StartingClass};
+use crate::quest::Quest;
+
+pub const SLOT_COUNT: usize = 3;
+const SAVE_DIR: &str = "saves";
+const SAVE_VERSION: u32 = 1;
+/// Item suffix marking starting-kit gear, in the slot a unique's key would take
+const STARTER_TAG: &str = "starter";
+/// World seed every run used before seeds were selectable
+pub const LEGACY_SEED: u32 = 12345;
+
+/// Summary shown for a filled slot in the main menu
+#[derive(Clone, Debug)]
+pub struct SlotInfo {
+    pub level: u32,
+    pub difficulty: Difficulty,
+    pub kills: u32,
+    pub gold: u32,
+    pub saved_at: u64, // Unix seconds
+}
+
+/// Everything restored when a slot is loaded
+pub struct SaveData {
+    pub seed: u32,
+    pub difficulty: Difficulty,
+    pub player: Player,
+    pub kills: u32,
+    pub play_time: f64, // Seconds
+    pub waypoint: Option<(f32, f32)>,
+    pub corpse: Option<Corpse>,
+    pub quest: Option<Quest>, // None in saves from before quests
+}
+
+fn slot_path(slot: usize) -> PathBuf {
+    PathBuf::from(SAVE_DIR).join(format!("slot{}.sav", slot + 1))
+}
+
+/// Write a run to a slot as plain `key=value` lines (repeated keys for lists)
+#[allow(clippy::too_many_arguments)] // One per saved piece of run state
+pub fn save_game(
+    slot: usize,
+    seed: u32,
+    difficulty: Difficulty,
+    player: &Player,
+    kills: u32,
+    play_time: f64,
+    waypoint: Option<(f32, f32)>,
+    corpse: Option<&Corpse>,
+    quest: &Quest,
+) -> io::Result<()> {
+    let saved_at = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+
+    let mut lines = vec![
+        format!("version={}", SAVE_VERSION),
+        format!("saved_at={}", saved_at),
+        format!("seed={}", seed),
+        format!("difficulty={}", difficulty.name()),
+        format!("kills={}", kills),
+        format!("play_time={}", play_time),
+        format!("class={}", player.class.name()),
+        format!("x={}", player.x),
+        format!("y={}", player.y),
+        format!("health={}", player.health),
+        format!("max_health={}", player.max_health()),
+        format!("max_mana={}", player.max_mana),
+        format!("gold={}", player.gold),
+        format!("level={}", player.level),
+        format!("xp={}", player.xp),
+        format!("pending_level_ups={}", player.pending_level_ups),
+        format!("strength={}", player.strength),
+        format!("vitality={}", player.vitality),
+        format!("dexterity={}", player.dexterity),
+        format!("magnetism={}", player.magnetism),
+        format!("weapon={}", encode_equipped(player.weapon.clone().map(Item::Weapon))),
+        format!("armor={}", encode_equipped(player.armor.clone().map(Item::Armor))),
+        format!("off_hand={}", encode_equipped(player.off_hand.clone().map(Item::Armor))),
+        format!("quest={},{},{}", quest.target.name(), quest.progress, quest.required),
+    ];
+    if let Some((x, y)) = waypoint {
+        lines.push(format!("waypoint={},{}", x, y));
+    }
+    lines.extend(player.inventory.items.iter().map(|item| format!("item={}", encode_item(item))));
+    if let Some(corpse) = corpse {
+        lines.push(format!("corpse={},{},{}", corpse.x, corpse.y, corpse.gold));
+        lines.extend(corpse.items.iter().map(|item| format!("corpse_item={}", encode_item(item))));
+    }
+
+    fs::create_dir_all(SAVE_DIR)?;
+    fs::write(slot_path(slot), lines.join("\n") + "\n")
+}
+
+/// Load a slot; None if it's empty or unreadable
+pub fn load_game(slot: usize) -> Option<SaveData> {
+    let fields = read_fields(slot)?;
+    let get = |key: &str| fields.get(key).and_then(|values| values.first()).map(String::as_str);
+
+    let (x, y) = (get("x")?.parse().ok()?, get("y")?.parse().ok()?);
+    // Saves from before classes get the default Warrior
+    let mut player = match get("class").and_then(StartingClass::from_name) {
+        Some(class) => Player::with_class(x, y, class),
+        None => Player::new(x, y),
+    };
+    player.set_max_health(get("max_health")?.parse().ok()?);
+    player.health = get("health")?.parse::<i32>().ok()?.clamp(1, player.max_health());
+    player.gold = get("gold")?.parse().ok()?;
+    // Leveling arrived after the first save format, so older saves start at level 1
+    let get_or = |key: &str, default: u32| get(key).and_then(|v| v.parse().ok()).unwrap_or(default);
+    player.level = get_or("level", 1);
+    player.xp = get_or("xp", 0);
+    player.pending_level_ups = get_or("pending_level_ups", 0);
+    player.strength = get_or("strength", 0);
+    player.vitality = get_or("vitality", 0);
+    player.dexterity = get_or("dexterity", 0);
+    player.magnetism = get_or("magnetism", 0);
+    player.max_mana = get_or("max_mana", player.max_mana);
+    player.weapon = match decode_item(get("weapon")?) {
+        Some(Item::Weapon(weapon)) => Some(weapon),
+        _ => None,
+    };
+    player.armor = match decode_item(get("armor")?) {
+        Some(Item::Armor(armor)) => Some(armor),
+        _ => None,
+    };
+    player.off_hand = match decode_item(get("off_hand")?) {
+        Some(Item::Armor(armor)) => Some(armor),
+        _ => None,
+    };
+    for value in fields.get("item").into_iter().flatten() {
+        if let Some(item) = decode_item(value) {
+            player.inventory.add_item(item);
+        }
+    }
+
+    let waypoint = get("waypoint").and_then(|value| {
+        let (x, y) = value.split_once(',')?;
+        Some((x.parse().ok()?, y.parse().ok()?))
+    });
+
+    let corpse = get("corpse").and_then(|value| {
+        let mut parts = value.split(',');
+        Some(Corpse {
+            x: parts.next()?.parse().ok()?,
+            y: parts.next()?.parse().ok()?,
+            gold: parts.next()?.parse().ok()?,
+            items: fields.get("corpse_item").into_iter().flatten().filter_map(|v| decode_item(v)).collect(),
+        })
+    });
+
+    let quest = get("quest").and_then(|value| {
+        let mut parts = value.split(',');
+        Some(Quest {
+            target: MonsterType::from_name(parts.next()?)?,
+            progress: parts.next()?.parse().ok()?,
+            required: parts.next()?.parse().ok()?,
+        })
+    });
+
+    Some(SaveData {
+        seed: get("seed").and_then(|v| v.parse().ok()).unwrap_or(LEGACY_SEED),
+        difficulty: read_difficulty(&fields),
+        player,
+        kills: get("kills")?.parse().ok()?,
+        // Play time arrived after the first save format; older runs start the clock at zero
+        play_time: get("play_time").and_then(|v| v.parse().ok()).unwrap_or(0.0),
+        waypoint,
+        corpse,
+        quest,
+    })
+}
+
+/// Menu summary for a slot without rebuilding the whole player
+pub fn slot_info(slot: usize) -> Option<SlotInfo> {
+    let fields = read_fields(slot)?;
+    let get = |key: &str| -> Option<u64> { fields.get(key)?.first()?.parse().ok() };
+    Some(SlotInfo {
+        level: get("level").unwrap_or(1) as u32,
+        difficulty: read_difficulty(&fields),
+        kills: get("kills")? as u32,
+        gold: get("gold")? as u32,
+        saved_at: get("saved_at")?,
+    })
+}
+
+pub fn delete_slot(slot: usize) -> io::Result<()> {
+    match fs::remove_file(slot_path(slot)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Saves from before difficulty tiers were all Normal
+fn read_difficulty(fields: &HashMap<String, Vec<String>>) -> Difficulty {
+    fields
+        .get("difficulty")
+        .and_then(|values| values.first())
+        .and_then(|name| Difficulty::from_name(name))
+        .unwrap_or(Difficulty::Normal)
+}
+
+fn read_fields(slot: usize) -> Option<HashMap<String, Vec<String>>> {
+    let contents = fs::read_to_string(slot_path(slot)).ok()?;
+    let mut fields: HashMap<String, Vec<String>> = HashMap::new();
+    for line in contents.lines() {
+        if let Some((key, value)) = line.split_once('=') {
+            fields.entry(key.trim().to_string()).or_default().push(value.trim().to_string());
+        }
+    }
+
+    // Refuse saves from a newer format rather than half-loading them
+    let version: u32 = fields.get("version")?.first()?.parse().ok()?;
+    (version <= SAVE_VERSION).then_some(fields)
+}
+
+/// `Weapon:Sword:87/120` style encoding (type names, not display names, so renames don't break saves).
+/// Uniques append their key: `Weapon:Axe:150/150:gravecleaver`; starting-kit gear appends `starter`.
+fn encode_item(item: &Item) -> String {
+    let (durability, max_durability) = item.durability();
+    let (kind, unique) = match item {
+        Item::Weapon(w) => (format!("Weapon:{:?}", w.kind), w.unique),
+        Item::Armor(a) => (format!("Armor:{:?}", a.kind), a.unique),
+    };
+    match unique {
+        Some(unique) => format!("{}:{}/{}:{}", kind, durability, max_durability, unique.key),
+        None if item.is_starter() => format!("{}:{}/{}:{}", kind, durability, max_durability, STARTER_TAG),
+        None => format!("{}:{}/{}", kind, durability, max_durability),
+    }
+}
+
+/// Equipment slots store `none` when empty
+fn encode_equipped(item: Option<Item>) -> String {
+    item.as_ref().map_or("none".to_string(), encode_item)
+}
+
+fn decode_item(value: &str) -> Option<Item> {
+    let mut parts = value.split(':');
+    let (category, kind, wear) = (parts.next()?, parts.next()?, parts.next()?);
+    let (durability, max_durability) = wear.split_once('/')?;
+    let durability: u32 = durability.parse().ok()?;
+    let max_durability: u32 = max_durability.parse().ok()?;
+    // An unknown unique key (say, one removed since) or one for another base loads as the plain item
+    let tag = parts.next();
+    let unique = tag.and_then(UniqueDef::by_key);
+    let starter = tag == Some(STARTER_TAG);
+
+    match category {
+        "Weapon" => {
+            let kind = match kind {
+                "Sword" => WeaponType::Sword,
+                "Axe" => WeaponType::Axe,
+                "Mace" => WeaponType::Mace,
+                "Bow" => WeaponType::Bow,
+                "Staff" => WeaponType::Staff,
+                _ => return None,
+            };
+            let unique = unique.filter(|u| matches!(u.stats, UniqueStats::Weapon { kind: k, .. } if k == kind));
+            Some(Item::Weapon(Weapon { kind, durability, max_durability, unique, starter }))
+        }
+        "Armor" => {
+            let kind = match kind {
+                "Leather" => ArmorType::Leather,
+                "Chainmail" => ArmorType::Chainmail,
+                "Platemail" => ArmorType::Platemail,
+                "Shield" => ArmorType::Shield,
+                _ => return None,
+            };
+            let unique = unique.filter(|u| matches!(u.stats, UniqueStats::Armor { kind: k, .. } if k == kind));
+            Some(Item::Armor(Armor { kind, durability, max_durability, unique, starter }))
+        }
+        _ => None,
+    }
+}
+
+/// Unix seconds as `YYYY-MM-DD HH:MM UTC`
+pub fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let minutes_of_day = secs % 86_400 / 60;
+
+    // Civil-from-days (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        minutes_of_day / 60,
+        minutes_of_day % 60
+    )
+}
@@ -0,0 +1,165 @@
+use std::fs;
+use std::io;
+
+use crate::ui::Palette;
+
+const SETTINGS_PATH: &str = "settings.cfg";
+
+/// A row on the settings screen, in display order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Setting {
+    AutoPickup,
+    ScreenShake,
+    DamageNumbers,
+    ColorBlind,
+    EdgeScroll,
+    Weather,
+    TextSize,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 7] = [
+        Setting::AutoPickup,
+        Setting::ScreenShake,
+        Setting::DamageNumbers,
+        Setting::ColorBlind,
+        Setting::EdgeScroll,
+        Setting::Weather,
+        Setting::TextSize,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Setting::AutoPickup => "Auto-pickup",
+            Setting::ScreenShake => "Screen shake",
+            Setting::DamageNumbers => "Damage numbers",
+            Setting::ColorBlind => "Color-blind palette",
+            Setting::EdgeScroll => "Edge scrolling",
+            Setting::Weather => "Weather effects",
+            Setting::TextSize => "Text size",
+        }
+    }
+
+    /// Key in the config file
+    fn key(&self) -> &'static str {
+        match self {
+            Setting::AutoPickup => "auto_pickup",
+            Setting::ScreenShake => "screen_shake",
+            Setting::DamageNumbers => "damage_numbers",
+            Setting::ColorBlind => "color_blind",
+            Setting::EdgeScroll => "edge_scroll",
+            Setting::Weather => "weather",
+            Setting::TextSize => "ui_scale",
+        }
+    }
+}
+
+/// Player preferences shared by every run, kept in `settings.cfg` next to the saves
+#[derive(Clone, Debug)]
+pub struct Settings {
+    pub auto_pickup: bool, // Off: items wait for the pickup key
+    pub screen_shake: bool,
+    pub damage_numbers: bool, // Floating numbers for hits dealt and taken
+    pub color_blind: bool,
+    pub edge_scroll: bool, // Mouse at the screen edge pans the camera
+    pub weather: bool,     // Snowfall and heat shimmer; off saves drawing them
+    pub ui_scale: f32,     // Multiplies HUD and inventory text and panels; one of UI_SCALES
+}
+
+impl Settings {
+    /// Text size steps, cycled in order on the settings screen
+    pub const UI_SCALES: [f32; 3] = [1.0, 1.25, 1.5];
+
+    pub fn new() -> Self {
+        Self {
+            auto_pickup: true,
+            screen_shake: true,
+            damage_numbers: true,
+            color_blind: false,
+            edge_scroll: false,
+            weather: true,
+            ui_scale: 1.0,
+        }
+    }
+
+    /// Read the config file; a missing file or unknown line keeps the defaults
+    pub fn load() -> Self {
+        let mut settings = Self::new();
+        let Ok(contents) = fs::read_to_string(SETTINGS_PATH) else {
+            return settings;
+        };
+        for line in contents.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let Some(setting) = Setting::ALL.into_iter().find(|s| s.key() == key.trim()) else {
+                continue;
+            };
+            let value = value.trim();
+            match settings.flag_mut(setting) {
+                Some(flag) => *flag = value.parse().unwrap_or(*flag),
+                // Hand-edited scales snap to the nearest step
+                None => {
+                    if let Ok(scale) = value.parse::<f32>() {
+                        settings.ui_scale = Self::UI_SCALES
+                            .into_iter()
+                            .min_by(|a, b| (a - scale).abs().total_cmp(&(b - scale).abs()))
+                            .unwrap_or(1.0);
+                    }
+                }
+            }
+        }
+        settings
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let lines: Vec<String> = Setting::ALL
+            .iter()
+            .map(|&s| match self.get(s) {
+                Some(on) => format!("{}={}", s.key(), on),
+                None => format!("{}={}", s.key(), self.ui_scale),
+            })
+            .collect();
+        fs::write(SETTINGS_PATH, lines.join("\n") + "\n")
+    }
+
+    /// On/off state of a toggle; None for the text size, which isn't one
+    pub fn get(&self, setting: Setting) -> Option<bool> {
+        match setting {
+            Setting::AutoPickup => Some(self.auto_pickup),
+            Setting::ScreenShake => Some(self.screen_shake),
+            Setting::DamageNumbers => Some(self.damage_numbers),
+            Setting::ColorBlind => Some(self.color_blind),
+            Setting::EdgeScroll => Some(self.edge_scroll),
+            Setting::Weather => Some(self.weather),
+            Setting::TextSize => None,
+        }
+    }
+
+    pub fn palette(&self) -> Palette {
+        if self.color_blind { Palette::ColorBlind } else { Palette::Standard }
+    }
+
+    /// Flip a toggle, or step the text size to the next scale (wrapping back to 100%)
+    pub fn toggle(&mut self, setting: Setting) {
+        match self.flag_mut(setting) {
+            Some(flag) => *flag = !*flag,
+            None => {
+                let current = Self::UI_SCALES.iter().position(|&s| s == self.ui_scale).unwrap_or(0);
+                self.ui_scale = Self::UI_SCALES[(current + 1) % Self::UI_SCALES.len()];
+            }
+        }
+    }
+
+    fn flag_mut(&mut self, setting: Setting) -> Option<&mut bool> {
+        match setting {
+            Setting::AutoPickup => Some(&mut self.auto_pickup),
+            Setting::ScreenShake => Some(&mut self.screen_shake),
+            Setting::DamageNumbers => Some(&mut self.damage_numbers),
+            Setting::ColorBlind => Some(&mut self.color_blind),
+            Setting::EdgeScroll => Some(&mut self.edge_scroll),
+            Setting::Weather => Some(&mut self.weather),
+            Setting::TextSize => None,
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::difficulty::Difficulty;
+use crate::monsters::{Affix, Monster, MonsterType};
+use crate::rng::GameRng;
+use crate::world::{self, Terrain, World};
+
+pub const CHUNK_SIZE: i32 = 8;
+const PACK_SPREAD: f32 = 1.8; // Distance of pack members from the leader
+
+struct ChunkRequest {
+    chunk: (i32, i32),
+    difficulty: Difficulty,
+}
+
+/// Fresh monsters for one chunk, ready to join the game
+pub struct ChunkSpawns {
+    pub chunk: (i32, i32),
+    pub monsters: Vec<Monster>,
+}
+
+/// Works out first-visit spawns for chunks on a worker thread with its own copy of the world
+/// (terrain is pure noise of the seed, so both copies agree). The worker only sees chunk
+/// coordinates and hands back monsters; which chunks are loaded stays with the main thread.
+pub struct Spawner {
+    requests: Sender<ChunkRequest>,
+    results: Receiver<ChunkSpawns>,
+    pending: HashSet<(i32, i32)>, // Requested but not yet collected
+}
+
+impl Spawner {
+    pub fn new(seed: u32) -> Self {
+        let (requests, request_rx) = mpsc::channel::<ChunkRequest>();
+        let (result_tx, results) = mpsc::channel();
+        // Ends once the Spawner (and so the request sender) is dropped
+        thread::spawn(move || {
+            let world = World::new(seed);
+            for request in request_rx {
+                let monsters = spawn_chunk(&world, seed, request.chunk, request.difficulty);
+                if result_tx.send(ChunkSpawns { chunk: request.chunk, monsters }).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { requests, results, pending: HashSet::new() }
+    }
+
+    /// Queue a chunk for spawning; already-queued chunks are ignored
+    pub fn request(&mut self, chunk: (i32, i32), difficulty: Difficulty) {
+        if self.pending.insert(chunk) && self.requests.send(ChunkRequest { chunk, difficulty }).is_err() {
+            self.pending.remove(&chunk); // Worker is gone; the chunk simply stays empty
+        }
+    }
+
+    pub fn is_pending(&self, chunk: (i32, i32)) -> bool {
+        self.pending.contains(&chunk)
+    }
+
+    /// Every chunk finished since the last call, without waiting on the rest
+    pub fn collect_ready(&mut self) -> Vec<ChunkSpawns> {
+        let ready: Vec<ChunkSpawns> = self.results.try_iter().collect();
+        for spawns in &ready {
+            self.pending.remove(&spawns.chunk);
+        }
+        ready
+    }
+}
+
+/// Monsters a chunk starts with, decided by a hash of its coordinates and the world seed
+fn spawn_chunk(world: &World, seed: u32, (chunk_x, chunk_y): (i32, i32), difficulty: Difficulty) -> Vec<Monster> {
+    // Use deterministic random based on chunk coords and the world seed
+    let mut hash = ((chunk_x.wrapping_mul(374761393)) ^ (chunk_y.wrapping_mul(668265263))) as u32;
+    hash = (hash ^ seed).wrapping_mul(0x9E37_79B1);
+    hash ^= hash >> 15;
+
+    // ~20% chance to spawn a monster in this chunk
+    if !hash.is_multiple_of(5) {
+        return Vec::new();
+    }
+
+    // Get center of chunk
+    let world_x = (chunk_x * CHUNK_SIZE) as f32 + (CHUNK_SIZE as f32 / 2.0);
+    let world_y = (chunk_y * CHUNK_SIZE) as f32 + (CHUNK_SIZE as f32 / 2.0);
+
+    // Add some randomness to position within chunk
+    let offset_x = ((hash >> 8) % (CHUNK_SIZE as u32)) as f32 - (CHUNK_SIZE as f32 / 2.0);
+    let offset_y = ((hash >> 16) % (CHUNK_SIZE as u32)) as f32 - (CHUNK_SIZE as f32 / 2.0);
+
+    let spawn_x = world_x + offset_x;
+    let spawn_y = world_y + offset_y;
+
+    // Never spawn inside town (this also keeps the player's start clear)
+    if world::is_in_town(spawn_x, spawn_y) {
+        return Vec::new();
+    }
+
+    // Get terrain and spawn appropriate monster
+    let terrain = world.get_terrain_at(spawn_x, spawn_y);
+    let monster_type = MonsterType::pick_for_terrain(terrain, hash >> 4);
+
+    // ~10% of spawns are elites with a random affix
+    let monster = if (hash >> 24).is_multiple_of(10) {
+        Monster::new_elite(spawn_x, spawn_y, monster_type, Affix::from_index(hash >> 28), difficulty)
+    } else {
+        Monster::new(spawn_x, spawn_y, monster_type, difficulty)
+    };
+    let mut monsters = vec![monster];
+
+    // ~1 in 4 spawns is a pack: 2-4 more of the same type around the first
+    if (hash >> 12).is_multiple_of(4) {
+        monsters.extend(spawn_pack(world, spawn_x, spawn_y, monster_type, terrain, hash, difficulty));
+        // The hash doubles as a pack id: it's unique to the chunk, and the same on every visit
+        if monsters.len() > 1 {
+            for monster in &mut monsters {
+                monster.pack = Some(hash);
+            }
+        }
+    }
+    monsters
+}
+
+/// Ring the leader with followers at evenly spaced angles so no two share a tile.
+/// Followers that would land in town, on a solid tile or on other terrain are skipped.
+fn spawn_pack(
+    world: &World,
+    leader_x: f32,
+    leader_y: f32,
+    monster_type: MonsterType,
+    terrain: Terrain,
+    hash: u32,
+    difficulty: Difficulty,
+) -> Vec<Monster> {
+    let mut pack = Vec::new();
+    let mut rng = GameRng::new(hash as u64);
+    let followers = rng.gen_range(2, 5);
+    let start_angle = rng.gen_f32() * std::f32::consts::TAU;
+
+    for i in 0..followers {
+        let angle = start_angle + i as f32 * std::f32::consts::TAU / followers as f32;
+        let distance = PACK_SPREAD * (0.8 + rng.gen_f32() * 0.4);
+        let x = leader_x + angle.cos() * distance;
+        let y = leader_y + angle.sin() * distance;
+
+        if world::is_in_town(x, y) || world.is_blocked(x, y) || world.get_terrain_at(x, y) != terrain {
+            continue;
+        }
+        pack.push(Monster::new(x, y, monster_type, difficulty));
+    }
+    pack
+}
@@ -1,36 +1,567 @@
 use macroquad::prelude::*;
 
-pub fn draw_health_bar(current: i32, max: i32) {
-    let bar_x = 20.0;
-    let bar_y = 20.0;
-    let bar_width = 200.0;
-    let bar_height = 25.0;
+use crate::camera::GameCamera;
+use crate::difficulty::Difficulty;
+use crate::player::{Player, Stat, StartingClass};
+use crate::quest::Quest;
+use crate::save::{format_timestamp, SlotInfo};
+use crate::settings::{Setting, Settings};
+use crate::world::{Terrain, World};
+
+const MINIMAP_TILE_PX: f32 = 3.0;
+const MINIMAP_MARGIN: i32 = 2; // Extra tiles sampled past the edge so sub-tile scrolling never shows a gap
+
+/// Status colors for bars and comparisons. The color-blind palette swaps red/green for blue/orange
+/// and marks uniques with a pattern rather than relying on their hue.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Palette {
+    Standard,
+    ColorBlind,
+}
+
+impl Palette {
+    /// Full health, fresh gear, an upgrade
+    pub fn good(&self) -> Color {
+        match self {
+            Palette::Standard => GREEN,
+            Palette::ColorBlind => Color::from_rgba(86, 180, 233, 255), // Sky blue
+        }
+    }
+
+    pub fn warning(&self) -> Color {
+        match self {
+            Palette::Standard => YELLOW,
+            Palette::ColorBlind => Color::from_rgba(240, 228, 66, 255),
+        }
+    }
+
+    /// Low health, nearly broken gear, a downgrade
+    pub fn bad(&self) -> Color {
+        match self {
+            Palette::Standard => RED,
+            Palette::ColorBlind => Color::from_rgba(230, 159, 0, 255), // Orange
+        }
+    }
+
+    /// Whether uniques get a pattern on top of their color
+    pub fn marks_uniques(&self) -> bool {
+        matches!(self, Palette::ColorBlind)
+    }
+}
+
+/// Terrain sampled around the player for the minimap, only refreshed when the player changes tile
+pub struct MinimapCache {
+    center: Option<(i32, i32)>, // Player tile the grid was sampled around
+    radius: i32,                // Sampled radius in tiles, margin included
+    tiles: Vec<Terrain>,        // Row-major, (2 * radius + 1)^2
+}
+
+impl MinimapCache {
+    pub fn new() -> Self {
+        Self { center: None, radius: 0, tiles: Vec::new() }
+    }
+
+    /// Resample only if the player crossed a tile boundary or the minimap was resized
+    pub fn refresh(&mut self, world: &World, player_x: f32, player_y: f32) {
+        let center = (player_x.round() as i32, player_y.round() as i32);
+        let radius = minimap_view_radius() + MINIMAP_MARGIN;
+        if self.center == Some(center) && self.radius == radius {
+            return;
+        }
+
+        self.center = Some(center);
+        self.radius = radius;
+        self.tiles.clear();
+        for y in (center.1 - radius)..=(center.1 + radius) {
+            for x in (center.0 - radius)..=(center.0 + radius) {
+                self.tiles.push(world.get_terrain_at(x as f32, y as f32));
+            }
+        }
+    }
+}
+
+/// Minimap side in pixels, scaled with the window
+fn minimap_size() -> f32 {
+    (screen_height() * 0.25).clamp(120.0, 240.0)
+}
+
+/// Tiles visible from the center to the minimap edge
+fn minimap_view_radius() -> i32 {
+    (minimap_size() / MINIMAP_TILE_PX / 2.0).ceil() as i32
+}
+
+/// Top-down terrain map in the bottom-right corner, centered on the player
+pub fn draw_minimap(cache: &MinimapCache, player_x: f32, player_y: f32) {
+    let Some((center_x, center_y)) = cache.center else {
+        return;
+    };
+    let size = minimap_size();
+    let left = screen_width() - size - 20.0;
+    let top = screen_height() - size - 20.0;
+    let mid_x = left + size / 2.0;
+    let mid_y = top + size / 2.0;
+
+    draw_rectangle(left, top, size, size, Color::from_rgba(0, 0, 0, 160));
+
+    let side = 2 * cache.radius + 1;
+    for (i, terrain) in cache.tiles.iter().enumerate() {
+        let tile_x = center_x - cache.radius + i as i32 % side;
+        let tile_y = center_y - cache.radius + i as i32 / side;
+        let x = mid_x + (tile_x as f32 - player_x - 0.5) * MINIMAP_TILE_PX;
+        let y = mid_y + (tile_y as f32 - player_y - 0.5) * MINIMAP_TILE_PX;
+        // Margin tiles are only there to fill gaps; skip anything outside the frame
+        if x < left || y < top || x + MINIMAP_TILE_PX > left + size || y + MINIMAP_TILE_PX > top + size {
+            continue;
+        }
+        let color = terrain.base_color();
+        draw_rectangle(x, y, MINIMAP_TILE_PX, MINIMAP_TILE_PX, Color::new(color.r, color.g, color.b, 0.8));
+    }
+
+    draw_circle(mid_x, mid_y, 3.0, RED);
+    draw_rectangle_lines(left, top, size, size, 2.0, GRAY);
+}
+
+/// Bar values eased toward the player's real ones so damage, healing and XP gains animate
+pub struct HudState {
+    displayed_health: f32,
+    displayed_xp: f32, // Fraction of the current level's bar
+    level: u32,        // Level `displayed_xp` belongs to
+}
+
+impl HudState {
+    const EASE_RATE: f32 = 8.0; // Per second; ~90% of the way there in 0.3s at any frame rate
+
+    pub fn new(player: &Player) -> Self {
+        Self {
+            displayed_health: player.health as f32,
+            displayed_xp: xp_fraction(player),
+            level: player.level,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32, player: &Player) {
+        let t = 1.0 - (-Self::EASE_RATE * dt).exp();
+        self.displayed_health += (player.health as f32 - self.displayed_health) * t;
+
+        // A level-up starts a fresh bar, so fill it from empty rather than draining backwards
+        if player.level != self.level {
+            self.level = player.level;
+            self.displayed_xp = 0.0;
+        }
+        self.displayed_xp += (xp_fraction(player) - self.displayed_xp) * t;
+    }
+}
+
+fn xp_fraction(player: &Player) -> f32 {
+    (player.xp as f32 / player.xp_to_next_level().max(1) as f32).min(1.0)
+}
+
+/// Health bar; the fill eases toward `current`, leaving a pale trail behind when damage is taken.
+/// The top-left HUD pieces all take the text size setting as `scale`.
+pub fn draw_health_bar(current: i32, max: i32, hud: &HudState, palette: Palette, scale: f32) {
+    let bar_x = 20.0 * scale;
+    let bar_y = 20.0 * scale;
+    let bar_width = 200.0 * scale;
+    let bar_height = 25.0 * scale;
 
     // Background
     draw_rectangle(bar_x, bar_y, bar_width, bar_height, DARKGRAY);
 
     // Health fill
-    let health_pct = current as f32 / max as f32;
+    // A non-positive max draws as an empty bar rather than dividing by zero
+    let health_pct = (current as f32 / max.max(1) as f32).clamp(0.0, 1.0);
+    let shown_pct = (hud.displayed_health / max.max(1) as f32).clamp(0.0, 1.0);
     let health_color = if health_pct > 0.5 {
-        GREEN
+        palette.good()
     } else if health_pct > 0.25 {
-        YELLOW
+        palette.warning()
     } else {
-        RED
+        palette.bad()
     };
-    draw_rectangle(bar_x, bar_y, bar_width * health_pct, bar_height, health_color);
+    if shown_pct > health_pct {
+        draw_rectangle(bar_x, bar_y, bar_width * health_pct, bar_height, health_color);
+        let trail_x = bar_x + bar_width * health_pct;
+        draw_rectangle(trail_x, bar_y, bar_width * (shown_pct - health_pct), bar_height, Color::new(1.0, 1.0, 1.0, 0.6));
+    } else {
+        draw_rectangle(bar_x, bar_y, bar_width * shown_pct, bar_height, health_color);
+    }
 
     // Border
     draw_rectangle_lines(bar_x, bar_y, bar_width, bar_height, 2.0, WHITE);
 
-    // Text
+    // Text always shows the true value, not the animated one
     let text = format!("{}/{}", current, max);
-    let text_dims = measure_text(&text, None, 20, 1.0);
+    let font_size = 20.0 * scale;
+    let text_dims = measure_text(&text, None, font_size as u16, 1.0);
     draw_text(
         &text,
         bar_x + bar_width / 2.0 - text_dims.width / 2.0,
-        bar_y + bar_height / 2.0 + text_dims.height / 2.0 - 2.0,
-        20.0,
+        bar_y + bar_height / 2.0 + text_dims.height / 2.0 - 2.0 * scale,
+        font_size,
         WHITE,
     );
 }
+
+/// Thin experience bar tucked under the health bar, with the level beside the gold count
+pub fn draw_xp_bar(level: u32, hud: &HudState, scale: f32) {
+    let bar_x = 20.0 * scale;
+    let bar_y = 47.0 * scale;
+    let bar_width = 200.0 * scale;
+    let bar_height = 5.0 * scale;
+    let pct = hud.displayed_xp.clamp(0.0, 1.0);
+
+    draw_rectangle(bar_x, bar_y, bar_width, bar_height, Color::from_rgba(40, 40, 60, 255));
+    draw_rectangle(bar_x, bar_y, bar_width * pct, bar_height, Color::from_rgba(140, 110, 255, 255));
+    let level_color = Color::from_rgba(180, 160, 255, 255);
+    draw_text(&format!("Level {}", level), bar_x + 120.0 * scale, 68.0 * scale, 20.0 * scale, level_color);
+}
+
+/// Current difficulty tier under the gold count
+pub fn draw_difficulty(difficulty: Difficulty, scale: f32) {
+    draw_text(difficulty.name(), 20.0 * scale, 90.0 * scale, 18.0 * scale, difficulty.color());
+}
+
+/// Current quest and its progress under the difficulty tier
+pub fn draw_quest(quest: &Quest, scale: f32) {
+    let x = 20.0 * scale;
+    draw_text(&quest.description(), x, 112.0 * scale, 18.0 * scale, Color::from_rgba(230, 200, 120, 255));
+    draw_text(&format!("{}/{}", quest.progress, quest.required), x, 130.0 * scale, 16.0 * scale, LIGHTGRAY);
+}
+
+/// Gold coin and count under the health bar
+pub fn draw_gold(gold: u32, scale: f32) {
+    let x = 30.0 * scale;
+    let y = 62.0 * scale;
+    draw_circle(x, y, 8.0 * scale, GOLD);
+    draw_circle_lines(x, y, 8.0 * scale, 1.5, Color::from_rgba(150, 110, 20, 255));
+    draw_text(&format!("{}", gold), x + 16.0 * scale, y + 6.0 * scale, 20.0 * scale, GOLD);
+}
+
+/// World seed in the bottom-left corner so it can be shared
+pub fn draw_seed(seed: u32) {
+    draw_text(&format!("Seed: {}", seed), 20.0, screen_height() - 20.0, 18.0, LIGHTGRAY);
+}
+
+const LOW_HEALTH: f32 = 0.25; // Health fraction below which the vignette shows
+
+/// Pulsing border that closes in as health drops below a quarter. Only a band around the edges
+/// is drawn, fading to nothing on its inner side, so the middle of the screen stays clear.
+pub fn draw_low_health_vignette(health_pct: f32, time: f32, palette: Palette) {
+    if health_pct >= LOW_HEALTH || health_pct <= 0.0 {
+        return;
+    }
+    let severity = 1.0 - health_pct / LOW_HEALTH; // 0 at the threshold, 1 at death's door
+
+    // The heartbeat quickens and strengthens as health falls
+    let rate = 4.0 + severity * 4.0;
+    let pulse = 0.65 + 0.35 * (time * rate).sin();
+    let alpha = (0.2 + 0.4 * severity) * pulse;
+
+    let (w, h) = (screen_width(), screen_height());
+    let band = w.min(h) * (0.12 + 0.08 * severity);
+    let base = palette.bad();
+    let outer = Color::new(base.r, base.g, base.b, alpha);
+    let inner = Color::new(base.r, base.g, base.b, 0.0);
+
+    // Outer corners then inner corners, clockwise from the top-left
+    let corners = [(0.0, 0.0), (w, 0.0), (w, h), (0.0, h)];
+    let insets = [(band, band), (w - band, band), (w - band, h - band), (band, h - band)];
+    let mut vertices: Vec<Vertex> = corners.iter().map(|&(x, y)| Vertex::new(x, y, 0.0, 0.0, 0.0, outer)).collect();
+    vertices.extend(insets.iter().map(|&(x, y)| Vertex::new(x, y, 0.0, 0.0, 0.0, inner)));
+    // One trapezoid per edge, two triangles each
+    let mut indices = Vec::with_capacity(24);
+    for i in 0..4u16 {
+        let next = (i + 1) % 4;
+        indices.extend_from_slice(&[i, next, 4 + next, i, 4 + next, 4 + i]);
+    }
+    draw_mesh(&Mesh { vertices, indices, texture: None });
+}
+
+/// Run clock under the quest
+pub fn draw_playtime(seconds: f64, scale: f32) {
+    draw_text(&format_playtime(seconds), 20.0 * scale, 152.0 * scale, 16.0 * scale, GRAY);
+}
+
+pub fn draw_free_look_hint() {
+    let text = "Free look - C to recenter";
+    let dims = measure_text(text, None, 18, 1.0);
+    draw_text(text, screen_width() / 2.0 - dims.width / 2.0, 30.0, 18.0, LIGHTGRAY);
+}
+
+/// `MM:SS`, growing an hours field (`H:MM:SS`) after the first hour
+pub fn format_playtime(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (hours, minutes, secs) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{:02}:{:02}", minutes, secs)
+    }
+}
+
+/// Chained-swing count beside the gold, fading as the window to keep it going runs out
+pub fn draw_combo(combo: u32, multiplier: f32, time_left: f32, scale: f32) {
+    if combo < 2 {
+        return;
+    }
+    let alpha = (time_left / 0.5).clamp(0.3, 1.0);
+    let text = format!("{}x combo (+{:.0}%)", combo, (multiplier - 1.0) * 100.0);
+    draw_text(&text, 240.0 * scale, 68.0 * scale, 20.0 * scale, Color::new(1.0, 0.55, 0.2, alpha));
+}
+
+/// Green droplet and label to the right of the health bar while poisoned
+pub fn draw_poison_indicator(scale: f32) {
+    let x = 240.0 * scale;
+    let y = 32.0 * scale;
+    let s = scale;
+    let color = Color::from_rgba(120, 220, 60, 255);
+    draw_circle(x, y + 2.0 * s, 7.0 * s, color);
+    draw_triangle(
+        Vec2::new(x - 6.0 * s, y),
+        Vec2::new(x + 6.0 * s, y),
+        Vec2::new(x, y - 12.0 * s),
+        color,
+    );
+    draw_text("Poisoned", x + 14.0 * s, y + 7.0 * s, 20.0 * s, color);
+}
+
+/// Debug readout in the top-right corner (toggled with F3)
+pub fn draw_debug(monsters: usize, ground_items: usize, chunks: usize, player_x: f32, player_y: f32) {
+    let lines = [
+        format!("FPS: {}", get_fps()),
+        format!("Monsters: {}", monsters),
+        format!("Ground items: {}", ground_items),
+        format!("Chunks: {}", chunks),
+        format!("Pos: {:.1}, {:.1}", player_x, player_y),
+    ];
+
+    let font_size = 16.0;
+    let line_height = 18.0;
+    let panel_w = 170.0;
+    let panel_h = lines.len() as f32 * line_height + 10.0;
+    let panel_x = screen_width() - panel_w - 20.0;
+    let panel_y = 20.0;
+
+    draw_rectangle(panel_x, panel_y, panel_w, panel_h, Color::from_rgba(0, 0, 0, 160));
+
+    for (i, line) in lines.iter().enumerate() {
+        draw_text(
+            line,
+            panel_x + 8.0,
+            panel_y + 18.0 + i as f32 * line_height,
+            font_size,
+            LIME,
+        );
+    }
+}
+
+/// Ring around the mouse cursor that fills clockwise as the attack cooldown recovers
+pub fn draw_cooldown_cursor(fraction: f32) {
+    let (x, y) = mouse_position();
+    let radius = 12.0;
+
+    draw_circle_lines(x, y, radius, 3.0, Color::from_rgba(0, 0, 0, 120));
+    if fraction >= 1.0 {
+        draw_circle_lines(x, y, radius, 2.0, Color::from_rgba(255, 255, 255, 200));
+    } else {
+        draw_arc(x, y, 32, radius - 1.5, -90.0, 3.0, 360.0 * fraction, Color::from_rgba(255, 170, 60, 220));
+    }
+}
+
+/// Screen-space selection ring with corner ticks around a targeted monster
+pub fn draw_target_reticle(x: f32, y: f32, radius: f32) {
+    let color = Color::from_rgba(255, 80, 80, 220);
+    draw_circle_lines(x, y, radius, 2.0, color);
+
+    let tick = 6.0;
+    for (dx, dy) in [(1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0)] {
+        draw_line(
+            x + dx * (radius - tick),
+            y + dy * (radius - tick),
+            x + dx * (radius + tick),
+            y + dy * (radius + tick),
+            2.0,
+            color,
+        );
+    }
+}
+
+/// Waypoint marker when on screen, otherwise an arrow clamped to the screen edge pointing at it
+pub fn draw_waypoint(camera: &GameCamera, world_x: f32, world_y: f32) {
+    let (x, y) = camera.world_to_screen(world_x, world_y);
+    let screen_w = screen_width();
+    let screen_h = screen_height();
+    let margin = 30.0;
+    let color = Color::from_rgba(80, 200, 255, 230);
+
+    if x >= margin && x <= screen_w - margin && y >= margin && y <= screen_h - margin {
+        // Beacon: ring on the ground with a post
+        draw_ellipse_lines(x, y, 14.0, 7.0, 0.0, 2.0, color);
+        draw_line(x, y, x, y - 40.0, 2.0, color);
+        draw_triangle(
+            Vec2::new(x, y - 40.0),
+            Vec2::new(x + 14.0, y - 34.0),
+            Vec2::new(x, y - 28.0),
+            color,
+        );
+        return;
+    }
+
+    // Project from screen center toward the waypoint until we hit the inset screen rectangle
+    let center_x = screen_w / 2.0;
+    let center_y = screen_h / 2.0;
+    let dx = x - center_x;
+    let dy = y - center_y;
+    let half_w = center_x - margin;
+    let half_h = center_y - margin;
+    let t_x = if dx.abs() > f32::EPSILON { half_w / dx.abs() } else { f32::INFINITY };
+    let t_y = if dy.abs() > f32::EPSILON { half_h / dy.abs() } else { f32::INFINITY };
+    let t = t_x.min(t_y);
+    let arrow_x = center_x + dx * t;
+    let arrow_y = center_y + dy * t;
+
+    let len = (dx * dx + dy * dy).sqrt();
+    let (dir_x, dir_y) = (dx / len, dy / len);
+    let (perp_x, perp_y) = (-dir_y, dir_x);
+    let size = 14.0;
+    draw_triangle(
+        Vec2::new(arrow_x + dir_x * size, arrow_y + dir_y * size),
+        Vec2::new(arrow_x - dir_x * size * 0.6 + perp_x * size * 0.7, arrow_y - dir_y * size * 0.6 + perp_y * size * 0.7),
+        Vec2::new(arrow_x - dir_x * size * 0.6 - perp_x * size * 0.7, arrow_y - dir_y * size * 0.6 - perp_y * size * 0.7),
+        color,
+    );
+}
+
+/// Title screen: New Game followed by one row per save slot
+pub fn draw_main_menu(selected: usize, slots: &[Option<SlotInfo>], seed_input: &str, difficulty: Difficulty, class: StartingClass) {
+    let screen_w = screen_width();
+    let screen_h = screen_height();
+
+    let title = "DIABLO CLONE";
+    let title_dims = measure_text(title, None, 64, 1.0);
+    draw_text(title, screen_w / 2.0 - title_dims.width / 2.0, screen_h / 2.0 - 160.0, 64.0, Color::from_rgba(200, 40, 40, 255));
+
+    let mut rows = vec!["New Game".to_string()];
+    rows.extend(slots.iter().enumerate().map(|(i, slot)| match slot {
+        Some(info) => format!(
+            "Slot {}: Level {} {}, {} kills, {} gold - {}",
+            i + 1,
+            info.level,
+            info.difficulty.name(),
+            info.kills,
+            info.gold,
+            format_timestamp(info.saved_at)
+        ),
+        None => format!("Slot {}: Empty", i + 1),
+    }));
+    rows.push("Settings".to_string());
+
+    let row_w = 520.0;
+    let row_h = 40.0;
+    let start_y = screen_h / 2.0 - 80.0;
+    for (i, row) in rows.iter().enumerate() {
+        let x = screen_w / 2.0 - row_w / 2.0;
+        let y = start_y + i as f32 * (row_h + 10.0);
+        let is_selected = i == selected;
+
+        let fill = if is_selected { Color::from_rgba(80, 50, 50, 255) } else { Color::from_rgba(40, 40, 50, 255) };
+        draw_rectangle(x, y, row_w, row_h, fill);
+        draw_rectangle_lines(x, y, row_w, row_h, 2.0, if is_selected { GOLD } else { GRAY });
+        draw_text(row, x + 15.0, y + 27.0, 22.0, if is_selected { WHITE } else { LIGHTGRAY });
+
+        // Seed field sits inside the New Game row
+        if i == 0 {
+            let seed = match seed_input.trim().parse::<u32>() {
+                Ok(_) => seed_input.trim().to_string(),
+                Err(_) if seed_input.is_empty() => "random".to_string(),
+                Err(_) => format!("{} (random)", seed_input),
+            };
+            let cursor = if is_selected && (get_time() * 2.0) as i64 % 2 == 0 { "_" } else { "" };
+            draw_text(&format!("Seed: {}{}", seed, cursor), x + 150.0, y + 27.0, 20.0, if is_selected { GOLD } else { GRAY });
+
+            let tier = format!("< {} >", difficulty.name());
+            let tier_dims = measure_text(&tier, None, 20, 1.0);
+            draw_text(&tier, x + row_w - tier_dims.width - 15.0, y + 27.0, 20.0, difficulty.color());
+
+            // Class sits just above the row, since the row itself is full
+            let class_text = format!("Class: < {} >", class.name());
+            draw_text(&class_text, x + 15.0, y - 8.0, 18.0, if is_selected { GOLD } else { GRAY });
+        }
+    }
+
+    let help = "W/S to choose | A/D difficulty | Q/E class | type digits for a seed | ENTER to start | DELETE to clear a slot";
+    let help_dims = measure_text(help, None, 18, 1.0);
+    draw_text(help, screen_w / 2.0 - help_dims.width / 2.0, screen_h - 40.0, 18.0, GRAY);
+}
+
+/// Toggle list for the settings screen, drawn over the menu or the paused game
+pub fn draw_settings(settings: &Settings, selected: usize) {
+    let screen_w = screen_width();
+    let screen_h = screen_height();
+    draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::from_rgba(0, 0, 0, 150));
+
+    let title = "SETTINGS";
+    let title_dims = measure_text(title, None, 56, 1.0);
+    draw_text(title, screen_w / 2.0 - title_dims.width / 2.0, screen_h / 2.0 - 110.0, 56.0, GOLD);
+
+    let row_w = 420.0;
+    let row_h = 44.0;
+    for (i, setting) in Setting::ALL.iter().enumerate() {
+        let x = screen_w / 2.0 - row_w / 2.0;
+        let y = screen_h / 2.0 - 60.0 + i as f32 * (row_h + 10.0);
+        let is_selected = i == selected;
+
+        let fill = if is_selected { Color::from_rgba(80, 50, 50, 255) } else { Color::from_rgba(40, 40, 50, 255) };
+        draw_rectangle(x, y, row_w, row_h, fill);
+        draw_rectangle_lines(x, y, row_w, row_h, 2.0, if is_selected { GOLD } else { GRAY });
+        draw_text(setting.label(), x + 15.0, y + 29.0, 24.0, if is_selected { WHITE } else { LIGHTGRAY });
+
+        let (state, color) = match settings.get(*setting) {
+            Some(true) => ("ON".to_string(), settings.palette().good()),
+            Some(false) => ("OFF".to_string(), GRAY),
+            None => (format!("{:.0}%", settings.ui_scale * 100.0), WHITE),
+        };
+        let state_dims = measure_text(&state, None, 24, 1.0);
+        draw_text(&state, x + row_w - state_dims.width - 15.0, y + 29.0, 24.0, color);
+    }
+
+    let help = "W/S to choose | ENTER or A/D to toggle | ESC to go back";
+    let help_dims = measure_text(help, None, 18, 1.0);
+    draw_text(help, screen_w / 2.0 - help_dims.width / 2.0, screen_h - 40.0, 18.0, GRAY);
+}
+
+/// Stat choice overlay shown while level-ups are pending
+pub fn draw_level_up(player: &Player) {
+    let screen_w = screen_width();
+    let screen_h = screen_height();
+    draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::from_rgba(0, 0, 0, 150));
+
+    let title = format!("LEVEL {}!", player.level);
+    let title_dims = measure_text(&title, None, 56, 1.0);
+    draw_text(&title, screen_w / 2.0 - title_dims.width / 2.0, screen_h / 2.0 - 110.0, 56.0, GOLD);
+
+    let subtitle = if player.pending_level_ups > 1 {
+        format!("Choose a stat to raise ({} points to spend)", player.pending_level_ups)
+    } else {
+        "Choose a stat to raise".to_string()
+    };
+    let subtitle_dims = measure_text(&subtitle, None, 22, 1.0);
+    draw_text(&subtitle, screen_w / 2.0 - subtitle_dims.width / 2.0, screen_h / 2.0 - 70.0, 22.0, LIGHTGRAY);
+
+    let row_w = 420.0;
+    let row_h = 44.0;
+    for (i, stat) in Stat::ALL.iter().enumerate() {
+        let current = match stat {
+            Stat::Strength => player.strength,
+            Stat::Vitality => player.vitality,
+            Stat::Dexterity => player.dexterity,
+            Stat::Magnetism => player.magnetism,
+        };
+        let x = screen_w / 2.0 - row_w / 2.0;
+        let y = screen_h / 2.0 - 40.0 + i as f32 * (row_h + 10.0);
+        draw_rectangle(x, y, row_w, row_h, Color::from_rgba(40, 40, 55, 240));
+        draw_rectangle_lines(x, y, row_w, row_h, 2.0, GOLD);
+        draw_text(&format!("[{}] {} ({})", i + 1, stat.name(), current), x + 15.0, y + 20.0, 22.0, WHITE);
+        draw_text(&stat.description(), x + 15.0, y + 38.0, 16.0, GRAY);
+    }
+}
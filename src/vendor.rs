@@ -0,0 +1,200 @@
+use macroquad::prelude::*;
+
+use crate::camera::GameCamera;
+use crate::combat::Item;
+use crate::inventory::{self, INVENTORY_SIZE};
+use crate::player::Player;
+use crate::rng::GameRng;
+use crate::ui::Palette;
+
+const STOCK_SIZE: usize = 5;
+const MAX_STOCK: usize = 20; // With the potion row, as many as the panel's left column fits
+pub const POTION_PRICE: u32 = 15;
+pub const POTION_HEAL: i32 = 25;
+const PANEL_W: f32 = 640.0;
+const PANEL_H: f32 = 560.0;
+const ROW_H: f32 = 17.0; // Fits a full 24-item backpack
+const ROW_W: f32 = 280.0;
+const LIST_TOP: f32 = 100.0;
+
+/// Merchant standing in town
+pub struct Vendor {
+    pub x: f32,
+    pub y: f32,
+    pub stock: Vec<Item>,
+}
+
+impl Vendor {
+    pub fn new(x: f32, y: f32, rng: &mut GameRng) -> Self {
+        let stock = (0..STOCK_SIZE).map(|_| Item::random(rng)).collect();
+        Self { x, y, stock }
+    }
+
+    /// Put an item the player sold on the shelf, clearing out the oldest stock once it's full
+    pub fn take_sold(&mut self, item: Item) {
+        if self.stock.len() >= MAX_STOCK {
+            self.stock.remove(0);
+        }
+        self.stock.push(item);
+    }
+
+    /// Whether the player is standing on the vendor's tile
+    pub fn is_player_on_tile(&self, player: &Player) -> bool {
+        (player.x - self.x).abs() < 0.6 && (player.y - self.y).abs() < 0.6
+    }
+
+    pub fn draw(&self, camera: &GameCamera) {
+        let (x, y) = camera.world_to_screen(self.x, self.y);
+
+        // Robe
+        draw_triangle(
+            Vec2::new(x, y - 30.0),
+            Vec2::new(x - 14.0, y),
+            Vec2::new(x + 14.0, y),
+            Color::from_rgba(110, 50, 140, 255),
+        );
+        // Head with hood
+        draw_circle(x, y - 34.0, 9.0, Color::from_rgba(220, 180, 140, 255));
+        draw_circle_lines(x, y - 34.0, 9.0, 2.0, Color::from_rgba(80, 30, 100, 255));
+
+        let label = "Vendor";
+        let dims = measure_text(label, None, 16, 1.0);
+        draw_text(label, x - dims.width / 2.0, y - 50.0, 16.0, GOLD);
+    }
+}
+
+/// Buy price for an item: its value, discounted for worn gear by its remaining durability
+pub fn buy_price(item: &Item) -> u32 {
+    let (durability, max_durability) = item.durability();
+    (item.value() * durability / max_durability.max(1)).max(1)
+}
+
+/// Vendors pay half of what they charge
+pub fn sell_price(item: &Item) -> u32 {
+    (buy_price(item) / 2).max(1)
+}
+
+/// A click inside the shop panel
+pub enum ShopClick {
+    BuyPotion,
+    Buy(usize),  // Index into vendor stock
+    Sell(usize), // Index into player backpack
+}
+
+fn panel_origin() -> (f32, f32) {
+    (
+        (screen_width() / 2.0 - PANEL_W / 2.0).max(0.0),
+        (screen_height() / 2.0 - PANEL_H / 2.0).max(0.0),
+    )
+}
+
+/// Top-left of a row in the stock (left) or backpack (right) column
+fn row_position(column: usize, row: usize) -> (f32, f32) {
+    let (panel_x, panel_y) = panel_origin();
+    (
+        panel_x + 20.0 + column as f32 * (ROW_W + 40.0),
+        panel_y + LIST_TOP + row as f32 * ROW_H,
+    )
+}
+
+fn row_under_mouse(column: usize, rows: usize) -> Option<usize> {
+    let (mouse_x, mouse_y) = mouse_position();
+    (0..rows).find(|&row| {
+        let (x, y) = row_position(column, row);
+        mouse_x >= x && mouse_x <= x + ROW_W && mouse_y >= y && mouse_y <= y + ROW_H
+    })
+}
+
+pub fn get_shop_click(vendor: &Vendor, player: &Player) -> Option<ShopClick> {
+    if !is_mouse_button_pressed(MouseButton::Left) {
+        return None;
+    }
+    // Row 0 of the left column is the potion, stock follows
+    match row_under_mouse(0, vendor.stock.len() + 1) {
+        Some(0) => return Some(ShopClick::BuyPotion),
+        Some(row) => return Some(ShopClick::Buy(row - 1)),
+        None => {}
+    }
+    row_under_mouse(1, player.inventory.count()).map(ShopClick::Sell)
+}
+
+/// The shop panel keeps its size; `scale` only applies to the item tooltip
+pub fn draw_shop_screen(vendor: &Vendor, player: &Player, palette: Palette, scale: f32) {
+    let screen_w = screen_width();
+    let screen_h = screen_height();
+
+    // Darken background
+    draw_rectangle(0.0, 0.0, screen_w, screen_h, Color::from_rgba(0, 0, 0, 180));
+
+    let (panel_x, panel_y) = panel_origin();
+    draw_rectangle(panel_x, panel_y, PANEL_W, PANEL_H, Color::from_rgba(45, 35, 50, 255));
+    draw_rectangle_lines(panel_x, panel_y, PANEL_W, PANEL_H, 2.0, GOLD);
+
+    draw_text("VENDOR", panel_x + 20.0, panel_y + 35.0, 32.0, GOLD);
+    draw_text(
+        &format!("Gold: {}", player.gold),
+        panel_x + PANEL_W - 160.0,
+        panel_y + 35.0,
+        24.0,
+        GOLD,
+    );
+
+    let (buy_x, _) = row_position(0, 0);
+    let (sell_x, _) = row_position(1, 0);
+    draw_text("For sale (click to buy):", buy_x, panel_y + LIST_TOP - 12.0, 18.0, GRAY);
+    draw_text(
+        &format!("Your backpack ({}/{}, click to sell):", player.inventory.count(), INVENTORY_SIZE),
+        sell_x,
+        panel_y + LIST_TOP - 12.0,
+        18.0,
+        GRAY,
+    );
+
+    let hovered_buy = row_under_mouse(0, vendor.stock.len() + 1);
+    let potion_color = if player.gold >= POTION_PRICE { PINK } else { DARKGRAY };
+    let potion_name = format!("Health Potion (+{} HP)", POTION_HEAL);
+    draw_shop_row(0, 0, &potion_name, POTION_PRICE, potion_color, hovered_buy == Some(0));
+    for (i, item) in vendor.stock.iter().enumerate() {
+        let price = buy_price(item);
+        let affordable = player.gold >= price && !player.inventory.is_full();
+        let color = if affordable { WHITE } else { DARKGRAY };
+        draw_shop_row(0, i + 1, item.name(), price, color, hovered_buy == Some(i + 1));
+    }
+
+    let hovered_sell = row_under_mouse(1, player.inventory.count());
+    for (i, item) in player.inventory.items.iter().enumerate() {
+        draw_shop_row(1, i, item.name(), sell_price(item), WHITE, hovered_sell == Some(i));
+    }
+
+    // Full tooltip for the hovered item, plus what it's worth here
+    let hovered = match (hovered_buy, hovered_sell) {
+        (Some(row), _) if row > 0 => vendor.stock.get(row - 1).map(|item| (item, format!("Buy for {}g", buy_price(item)))),
+        (_, Some(row)) => player.inventory.items.get(row).map(|item| (item, format!("Sells for {}g", sell_price(item)))),
+        _ => None,
+    };
+    if let Some((item, price)) = hovered {
+        let (mouse_x, mouse_y) = mouse_position();
+        let value = format!("Value: {}g | {}", item.value(), price);
+        inventory::draw_tooltip(mouse_x + 15.0, mouse_y + 15.0, item, player, Some(&value), palette, scale);
+    }
+
+    draw_text(
+        "Potions are drunk on purchase | Press ESC or E to leave",
+        panel_x + 20.0,
+        panel_y + PANEL_H - 20.0,
+        14.0,
+        GRAY,
+    );
+}
+
+fn draw_shop_row(column: usize, row: usize, name: &str, price: u32, color: Color, hovered: bool) {
+    let (x, y) = row_position(column, row);
+    if hovered {
+        draw_rectangle(x, y, ROW_W, ROW_H, Color::from_rgba(80, 70, 100, 255));
+    }
+    draw_text(name, x + 6.0, y + ROW_H - 4.0, 15.0, color);
+
+    let price_text = format!("{}g", price);
+    let dims = measure_text(&price_text, None, 15, 1.0);
+    draw_text(&price_text, x + ROW_W - dims.width - 6.0, y + ROW_H - 4.0, 15.0, GOLD);
+}
@@ -1,8 +1,44 @@
 use macroquad::prelude::*;
 use noise::{NoiseFn, Perlin};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 
+use crate::balance;
 use crate::camera::{GameCamera, TILE_HEIGHT, TILE_WIDTH};
 
+/// Tiles around the player that are fully lit; light fades out over `VISION_FALLOFF` beyond it
+pub const VISION_RADIUS: f32 = 9.0;
+const VISION_FALLOFF: f32 = 5.0;
+const MIN_LIGHT: f32 = 0.25; // Far away is dim, never pitch black
+
+/// Brightness (MIN_LIGHT-1.0) at a world-space offset from the player, smoothstepped so there's no hard edge
+pub fn vision_light(dx: f32, dy: f32) -> f32 {
+    let dist = (dx * dx + dy * dy).sqrt();
+    let t = ((dist - VISION_RADIUS) / VISION_FALLOFF).clamp(0.0, 1.0);
+    let fade = t * t * (3.0 - 2.0 * t);
+    1.0 - fade * (1.0 - MIN_LIGHT)
+}
+
+/// Scale a color's RGB by a light level, keeping its alpha
+pub fn shade(color: Color, light: f32) -> Color {
+    Color::new(color.r * light, color.g * light, color.b * light, color.a)
+}
+
+/// Soft ground shadow under an entity; `radius` is the half-width in pixels (iso ellipse, half as tall)
+pub fn draw_shadow(screen_x: f32, screen_y: f32, radius: f32) {
+    draw_ellipse(screen_x, screen_y, radius, radius / 2.0, 0.0, Color::new(0.0, 0.0, 0.0, 0.25));
+}
+
+/// Radius in tiles of the safe town around the origin
+pub const TOWN_RADIUS: f32 = 7.0;
+const DECORATION_JITTER: f32 = 0.3; // Max offset from the tile center in tiles; collision stays per tile
+const DEBUG_GRID_RADIUS: i32 = 8; // Tiles labeled around the player by the F3 grid
+
+/// Whether a world position lies inside the town (no spawns, no monster attacks)
+pub fn is_in_town(x: f32, y: f32) -> bool {
+    x * x + y * y <= TOWN_RADIUS * TOWN_RADIUS
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Terrain {
     Grass,
@@ -11,6 +47,16 @@ pub enum Terrain {
 }
 
 impl Terrain {
+    pub const ALL: [Terrain; 3] = [Terrain::Grass, Terrain::Desert, Terrain::Snow];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Terrain::Grass => "Grass",
+            Terrain::Desert => "Desert",
+            Terrain::Snow => "Snow",
+        }
+    }
+
     pub fn base_color(&self) -> Color {
         match self {
             Terrain::Grass => Color::from_rgba(80, 160, 80, 255),
@@ -18,6 +64,47 @@ impl Terrain {
             Terrain::Snow => Color::from_rgba(240, 245, 255, 255),
         }
     }
+
+    /// Faint full-screen wash while standing here; grass is neutral (zero alpha)
+    pub fn ambient_tint(&self) -> Color {
+        match self {
+            Terrain::Grass => Color::new(1.0, 1.0, 1.0, 0.0),
+            Terrain::Desert => Color::new(1.0, 0.6, 0.2, 0.06),
+            Terrain::Snow => Color::new(0.45, 0.65, 1.0, 0.07),
+        }
+    }
+
+    /// Multiplier on walking speed while standing on this terrain
+    pub fn speed_modifier(&self) -> f32 {
+        balance::terrain_speed(*self)
+    }
+
+    /// Built-in speed modifier; `balance.cfg` can override it
+    pub fn default_speed_modifier(&self) -> f32 {
+        match self {
+            Terrain::Grass => 1.0,
+            Terrain::Desert => 0.9, // Loose sand
+            Terrain::Snow => 0.75,  // Deep snow
+        }
+    }
+
+    /// Decoration noise must exceed this for a tile to be decorated - lower is denser
+    pub fn decoration_threshold(&self) -> f64 {
+        match self {
+            Terrain::Grass => 0.55,  // Lush
+            Terrain::Desert => 0.8,  // Sparse
+            Terrain::Snow => 0.7,
+        }
+    }
+
+    /// Decorations that can appear in this biome, with relative weights
+    pub fn decorations(&self) -> &'static [(Decoration, u32)] {
+        match self {
+            Terrain::Grass => &[(Decoration::Rock, 3), (Decoration::Tree, 4), (Decoration::Flower, 3)],
+            Terrain::Desert => &[(Decoration::Cactus, 4), (Decoration::Bones, 3), (Decoration::DeadBush, 3)],
+            Terrain::Snow => &[(Decoration::SnowyRock, 3), (Decoration::SnowyTree, 4), (Decoration::IceShard, 2)],
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -25,61 +112,120 @@ pub enum Decoration {
     // Grass decorations
     Rock,
     Tree,
+    Flower,
     // Desert decorations
     Cactus,
     Bones,
+    DeadBush,
     // Snow decorations
     SnowyRock,
     SnowyTree,
+    IceShard,
 }
 
 impl Decoration {
-    pub fn draw(&self, screen_x: f32, screen_y: f32) {
+    /// Whether this decoration blocks movement
+    pub fn is_solid(&self) -> bool {
+        match self {
+            Decoration::Rock | Decoration::Tree | Decoration::Cactus => true,
+            Decoration::SnowyRock | Decoration::SnowyTree | Decoration::IceShard => true,
+            // Flat or flimsy - walk over them
+            Decoration::Bones | Decoration::Flower | Decoration::DeadBush => false,
+        }
+    }
+
+    /// Draw at a screen position; `light` (0-1) dims it to match the tile it stands on
+    pub fn draw(&self, screen_x: f32, screen_y: f32, light: f32) {
+        let c = |color: Color| shade(color, light);
         match self {
             Decoration::Rock => {
-                draw_poly(screen_x, screen_y - 5.0, 5, 8.0, 0.0, GRAY);
+                draw_poly(screen_x, screen_y - 5.0, 5, 8.0, 0.0, c(GRAY));
             }
             Decoration::Tree => {
                 // Trunk
-                draw_rectangle(screen_x - 3.0, screen_y - 20.0, 6.0, 20.0, Color::from_rgba(101, 67, 33, 255));
+                draw_rectangle(screen_x - 3.0, screen_y - 20.0, 6.0, 20.0, c(Color::from_rgba(101, 67, 33, 255)));
                 // Foliage
-                draw_poly(screen_x, screen_y - 35.0, 3, 15.0, 180.0, Color::from_rgba(34, 139, 34, 255));
+                draw_poly(screen_x, screen_y - 35.0, 3, 15.0, 180.0, c(Color::from_rgba(34, 139, 34, 255)));
+            }
+            Decoration::Flower => {
+                // Stem and bloom
+                draw_line(screen_x, screen_y, screen_x, screen_y - 8.0, 1.5, c(Color::from_rgba(40, 120, 40, 255)));
+                draw_circle(screen_x, screen_y - 9.0, 3.0, c(Color::from_rgba(230, 90, 160, 255)));
+                draw_circle(screen_x, screen_y - 9.0, 1.2, c(YELLOW));
             }
             Decoration::Cactus => {
                 // Main body
-                draw_rectangle(screen_x - 4.0, screen_y - 25.0, 8.0, 25.0, Color::from_rgba(60, 140, 60, 255));
+                draw_rectangle(screen_x - 4.0, screen_y - 25.0, 8.0, 25.0, c(Color::from_rgba(60, 140, 60, 255)));
                 // Arms
-                draw_rectangle(screen_x - 12.0, screen_y - 20.0, 8.0, 5.0, Color::from_rgba(60, 140, 60, 255));
-                draw_rectangle(screen_x + 4.0, screen_y - 15.0, 8.0, 5.0, Color::from_rgba(60, 140, 60, 255));
+                draw_rectangle(screen_x - 12.0, screen_y - 20.0, 8.0, 5.0, c(Color::from_rgba(60, 140, 60, 255)));
+                draw_rectangle(screen_x + 4.0, screen_y - 15.0, 8.0, 5.0, c(Color::from_rgba(60, 140, 60, 255)));
             }
             Decoration::Bones => {
-                draw_line(screen_x - 8.0, screen_y - 2.0, screen_x + 8.0, screen_y - 2.0, 3.0, Color::from_rgba(230, 230, 210, 255));
-                draw_line(screen_x - 5.0, screen_y - 6.0, screen_x + 5.0, screen_y + 2.0, 2.0, Color::from_rgba(230, 230, 210, 255));
+                draw_line(screen_x - 8.0, screen_y - 2.0, screen_x + 8.0, screen_y - 2.0, 3.0, c(Color::from_rgba(230, 230, 210, 255)));
+                draw_line(screen_x - 5.0, screen_y - 6.0, screen_x + 5.0, screen_y + 2.0, 2.0, c(Color::from_rgba(230, 230, 210, 255)));
+            }
+            Decoration::DeadBush => {
+                let color = c(Color::from_rgba(130, 95, 60, 255));
+                draw_line(screen_x, screen_y, screen_x - 7.0, screen_y - 10.0, 1.5, color);
+                draw_line(screen_x, screen_y, screen_x + 6.0, screen_y - 12.0, 1.5, color);
+                draw_line(screen_x, screen_y, screen_x + 1.0, screen_y - 14.0, 1.5, color);
+                draw_line(screen_x - 4.0, screen_y - 6.0, screen_x - 9.0, screen_y - 7.0, 1.0, color);
             }
             Decoration::SnowyRock => {
-                draw_poly(screen_x, screen_y - 5.0, 5, 8.0, 0.0, Color::from_rgba(180, 180, 190, 255));
+                draw_poly(screen_x, screen_y - 5.0, 5, 8.0, 0.0, c(Color::from_rgba(180, 180, 190, 255)));
                 // Snow cap
-                draw_poly(screen_x, screen_y - 8.0, 5, 5.0, 0.0, WHITE);
+                draw_poly(screen_x, screen_y - 8.0, 5, 5.0, 0.0, c(WHITE));
             }
             Decoration::SnowyTree => {
                 // Trunk
-                draw_rectangle(screen_x - 3.0, screen_y - 20.0, 6.0, 20.0, Color::from_rgba(101, 67, 33, 255));
+                draw_rectangle(screen_x - 3.0, screen_y - 20.0, 6.0, 20.0, c(Color::from_rgba(101, 67, 33, 255)));
                 // Snow-covered foliage
-                draw_poly(screen_x, screen_y - 35.0, 3, 15.0, 180.0, Color::from_rgba(220, 240, 220, 255));
+                draw_poly(screen_x, screen_y - 35.0, 3, 15.0, 180.0, c(Color::from_rgba(220, 240, 220, 255)));
+            }
+            Decoration::IceShard => {
+                // Tall crystal leaning off a shorter one
+                draw_triangle(
+                    Vec2::new(screen_x - 5.0, screen_y),
+                    Vec2::new(screen_x + 5.0, screen_y),
+                    Vec2::new(screen_x + 1.0, screen_y - 26.0),
+                    c(Color::from_rgba(150, 210, 240, 230)),
+                );
+                draw_triangle(
+                    Vec2::new(screen_x + 2.0, screen_y),
+                    Vec2::new(screen_x + 10.0, screen_y),
+                    Vec2::new(screen_x + 9.0, screen_y - 14.0),
+                    c(Color::from_rgba(190, 235, 255, 230)),
+                );
             }
         }
     }
 }
 
-pub struct WorldTile {
-    pub terrain: Terrain,
-    pub decoration: Option<Decoration>,
-}
-
 pub struct World {
     noise: Perlin,
     decoration_noise: Perlin,
     seed: u32,
+    terrain_mesh: TerrainMesh,
+}
+
+const TERRAIN_MESH_MARGIN: i32 = 2; // Extra tiles baked past the view so a stale frame never shows a gap
+const TILE_VERTICES: usize = 12; // Diamond quad plus two thin grid-line quads
+const TILE_INDICES: usize = 18;
+const TILES_PER_BATCH: usize = 256; // Stays under macroquad's 5000-index draw call cap
+
+/// Ground tiles around the camera with their blended colors baked in, rebuilt only when the
+/// camera enters a new tile. Drawing then skips the noise lookups and submits a few meshes.
+struct TerrainMesh {
+    center: Option<(i32, i32)>, // Camera tile the mesh was built around
+    radius: i32,                // Built radius in tiles, margin included
+    tiles: Vec<(i32, i32)>,     // World tile of each TILE_VERTICES block below
+    vertices: Vec<Vertex>,      // Positioned relative to the center tile, unlit
+}
+
+impl TerrainMesh {
+    fn new() -> Self {
+        Self { center: None, radius: 0, tiles: Vec::new(), vertices: Vec::new() }
+    }
 }
 
 impl World {
@@ -88,9 +234,50 @@ impl World {
             noise: Perlin::new(seed),
             decoration_noise: Perlin::new(seed.wrapping_add(1000)),
             seed,
+            terrain_mesh: TerrainMesh::new(),
         }
     }
 
+    /// Rebake the ground mesh if the camera crossed into a new tile or the window was resized
+    pub fn refresh_terrain_mesh(&mut self, camera: &GameCamera) {
+        let center = (camera.x.round() as i32, camera.y.round() as i32);
+        let radius = visible_tile_radius() + TERRAIN_MESH_MARGIN;
+        if self.terrain_mesh.center == Some(center) && self.terrain_mesh.radius == radius {
+            return;
+        }
+
+        let side = (2 * radius + 1) as usize;
+        let mut tiles = Vec::with_capacity(side * side);
+        let mut vertices = Vec::with_capacity(side * side * TILE_VERTICES);
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let (world_x, world_y) = (center.0 + dx, center.1 + dy);
+                // Same iso offset `world_to_screen` applies, relative to the center tile
+                let x = (dx - dy) as f32 * (TILE_WIDTH / 2.0);
+                let y = (dx + dy) as f32 * (TILE_HEIGHT / 2.0);
+                tiles.push((world_x, world_y));
+                push_tile_vertices(&mut vertices, x, y, self.tile_color(world_x, world_y));
+            }
+        }
+
+        self.terrain_mesh = TerrainMesh { center: Some(center), radius, tiles, vertices };
+    }
+
+    /// Blended terrain color, paved over inside town
+    fn tile_color(&self, world_x: i32, world_y: i32) -> Color {
+        let mut color = self.get_blended_color(world_x as f32, world_y as f32);
+        if is_in_town(world_x as f32, world_y as f32) {
+            color = lerp_color(color, Color::from_rgba(125, 120, 110, 255), 0.75);
+
+            // Outermost ring of town tiles marks the boundary
+            let dist = ((world_x * world_x + world_y * world_y) as f32).sqrt();
+            if dist > TOWN_RADIUS - 1.0 {
+                color = lerp_color(color, Color::from_rgba(200, 170, 90, 255), 0.6);
+            }
+        }
+        color
+    }
+
     pub fn get_terrain_at(&self, x: f32, y: f32) -> Terrain {
         let scale = 0.05; // Controls biome size
         let noise_val = self.noise.get([x as f64 * scale, y as f64 * scale]);
@@ -134,78 +321,265 @@ impl World {
     }
 
     fn get_decoration_at(&self, x: i32, y: i32) -> Option<Decoration> {
-        let dec_noise = self.decoration_noise.get([x as f64 * 0.5, y as f64 * 0.5]);
-
-        // Only spawn decorations ~10% of tiles
-        if dec_noise < 0.7 {
+        // Town streets are kept clear
+        if is_in_town(x as f32, y as f32) {
             return None;
         }
 
         let terrain = self.get_terrain_at(x as f32, y as f32);
-        let hash = ((x.wrapping_mul(374761393) ^ y.wrapping_mul(668265263)) as u32).wrapping_add(self.seed);
-
-        match terrain {
-            Terrain::Grass => {
-                if hash % 2 == 0 {
-                    Some(Decoration::Rock)
-                } else {
-                    Some(Decoration::Tree)
-                }
+        let dec_noise = self.decoration_noise.get([x as f64 * 0.5, y as f64 * 0.5]);
+        if dec_noise < terrain.decoration_threshold() {
+            return None;
+        }
+
+        // Pick a weighted variant
+        let hash = self.tile_hash(x, y);
+        let table = terrain.decorations();
+        let total: u32 = table.iter().map(|&(_, weight)| weight).sum();
+        let mut roll = hash % total;
+        for &(decoration, weight) in table {
+            if roll < weight {
+                return Some(decoration);
             }
-            Terrain::Desert => {
-                if hash % 2 == 0 {
-                    Some(Decoration::Cactus)
-                } else {
-                    Some(Decoration::Bones)
-                }
+            roll -= weight;
+        }
+        None
+    }
+
+    /// Per-tile hash mixed with the world seed; extra mixing so neighbouring tiles don't correlate
+    fn tile_hash(&self, x: i32, y: i32) -> u32 {
+        let mut hash = ((x.wrapping_mul(374761393) ^ y.wrapping_mul(668265263)) as u32).wrapping_add(self.seed);
+        hash ^= hash >> 13;
+        hash = hash.wrapping_mul(1274126177);
+        hash ^= hash >> 16;
+        hash
+    }
+
+    /// Where inside its tile a decoration stands, so forests don't look grid-aligned. Derived from
+    /// the tile hash, so it's the same every frame and every session with the same seed.
+    fn decoration_offset(&self, x: i32, y: i32) -> (f32, f32) {
+        let hash = self.tile_hash(x, y);
+        let unit = |bits: u32| (bits & 0xFF) as f32 / 255.0 * 2.0 - 1.0;
+        (unit(hash >> 16) * DECORATION_JITTER, unit(hash >> 24) * DECORATION_JITTER)
+    }
+
+    /// Walking speed multiplier for the tile under a world position (town streets are paved)
+    pub fn speed_modifier_at(&self, x: f32, y: f32) -> f32 {
+        let (tile_x, tile_y) = (x.round(), y.round());
+        if is_in_town(tile_x, tile_y) {
+            1.0
+        } else {
+            self.get_terrain_at(tile_x, tile_y).speed_modifier()
+        }
+    }
+
+    /// Whether the tile containing this world position holds a solid decoration
+    pub fn is_blocked(&self, x: f32, y: f32) -> bool {
+        self.is_tile_blocked(x.round() as i32, y.round() as i32)
+    }
+
+    /// Whether nothing solid stands on the straight line between two world points. Walks the tiles
+    /// the segment crosses in order (DDA) and stops at the first blocked one; the end tiles aren't
+    /// checked, since whoever is looking and whatever is seen stand there.
+    pub fn line_of_sight(&self, x0: f32, y0: f32, x1: f32, y1: f32) -> bool {
+        // Tiles are centered on integer coordinates, so shift half a tile for floor() to pick them
+        let (start_x, start_y) = (x0 + 0.5, y0 + 0.5);
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        let (mut tile_x, mut tile_y) = (start_x.floor() as i32, start_y.floor() as i32);
+        let (end_x, end_y) = ((x1 + 0.5).floor() as i32, (y1 + 0.5).floor() as i32);
+
+        // Distance along the segment (0..1) to the next tile boundary on each axis, and per tile after that
+        let axis = |start: f32, tile: i32, delta: f32| -> (i32, f32, f32) {
+            if delta > 0.0 {
+                (1, (tile as f32 + 1.0 - start) / delta, 1.0 / delta)
+            } else if delta < 0.0 {
+                (-1, (start - tile as f32) / -delta, -1.0 / delta)
+            } else {
+                (0, f32::INFINITY, f32::INFINITY)
             }
-            Terrain::Snow => {
-                if hash % 2 == 0 {
-                    Some(Decoration::SnowyRock)
-                } else {
-                    Some(Decoration::SnowyTree)
-                }
+        };
+        let (step_x, mut next_x, per_x) = axis(start_x, tile_x, dx);
+        let (step_y, mut next_y, per_y) = axis(start_y, tile_y, dy);
+
+        // One step per tile boundary crossed, so this ends on the far tile even with float error
+        let steps = (end_x - tile_x).abs() + (end_y - tile_y).abs();
+        for _ in 1..steps {
+            if next_x < next_y {
+                tile_x += step_x;
+                next_x += per_x;
+            } else {
+                tile_y += step_y;
+                next_y += per_y;
+            }
+            if self.is_tile_blocked(tile_x, tile_y) {
+                return false;
             }
         }
+        true
     }
 
-    pub fn draw(&self, camera: &GameCamera) {
-        let screen_w = screen_width();
-        let screen_h = screen_height();
+    fn is_tile_blocked(&self, tile_x: i32, tile_y: i32) -> bool {
+        self.get_decoration_at(tile_x, tile_y).is_some_and(|d| d.is_solid())
+    }
 
-        // Calculate visible tile range
-        let tiles_x = (screen_w / TILE_WIDTH) as i32 + 4;
-        let tiles_y = (screen_h / TILE_HEIGHT) as i32 + 4;
+    /// A* over the tile grid, searching at most `max_radius` tiles from `start`.
+    /// Returns the tiles to walk through in order (excluding `start`), or None if unreachable.
+    pub fn find_path(&self, start: (i32, i32), goal: (i32, i32), max_radius: i32) -> Option<Vec<(i32, i32)>> {
+        // Octile distance: 10 per straight step, 14 per diagonal step
+        let heuristic = |(x, y): (i32, i32)| {
+            let dx = (x - goal.0).abs();
+            let dy = (y - goal.1).abs();
+            10 * dx.max(dy) + 4 * dx.min(dy)
+        };
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+        let mut g_score: HashMap<(i32, i32), i32> = HashMap::new();
+
+        g_score.insert(start, 0);
+        open.push(Reverse((heuristic(start), start)));
+
+        while let Some(Reverse((_, current))) = open.pop() {
+            if current == goal {
+                let mut path = vec![current];
+                let mut node = current;
+                while let Some(&prev) = came_from.get(&node) {
+                    if prev == start {
+                        break;
+                    }
+                    path.push(prev);
+                    node = prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
 
-        let cam_tile_x = camera.x as i32;
-        let cam_tile_y = camera.y as i32;
+            let current_g = g_score[&current];
+            for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                let next = (current.0 + dx, current.1 + dy);
+                if (next.0 - start.0).abs() > max_radius || (next.1 - start.1).abs() > max_radius {
+                    continue;
+                }
+                // The goal is always enterable (the target may stand on a decoration)
+                if next != goal && self.is_tile_blocked(next.0, next.1) {
+                    continue;
+                }
+                // No cutting corners past solid tiles on diagonal steps
+                if dx != 0
+                    && dy != 0
+                    && (self.is_tile_blocked(current.0 + dx, current.1)
+                        || self.is_tile_blocked(current.0, current.1 + dy))
+                {
+                    continue;
+                }
 
-        // Draw tiles
-        for dy in -tiles_y..=tiles_y {
-            for dx in -tiles_x..=tiles_x {
-                let world_x = cam_tile_x + dx;
-                let world_y = cam_tile_y + dy;
+                let step_cost = if dx != 0 && dy != 0 { 14 } else { 10 };
+                let tentative_g = current_g + step_cost;
+                if g_score.get(&next).is_none_or(|&g| tentative_g < g) {
+                    g_score.insert(next, tentative_g);
+                    came_from.insert(next, current);
+                    open.push(Reverse((tentative_g + heuristic(next), next)));
+                }
+            }
+        }
 
-                let (screen_x, screen_y) = camera.world_to_screen(world_x as f32, world_y as f32);
+        None
+    }
 
-                // Skip if off screen
-                if screen_x < -TILE_WIDTH || screen_x > screen_w + TILE_WIDTH
-                    || screen_y < -TILE_HEIGHT || screen_y > screen_h + TILE_HEIGHT
-                {
+    /// Draw the ground, darkened with distance from the player. Tiles come from the baked mesh;
+    /// only the screen offset and lighting are applied per frame since both follow moving things.
+    pub fn draw(&self, camera: &GameCamera, player_x: f32, player_y: f32) {
+        let mesh = &self.terrain_mesh;
+        let Some((center_x, center_y)) = mesh.center else {
+            return;
+        };
+        let (screen_w, screen_h) = (screen_width(), screen_height());
+        let (origin_x, origin_y) = camera.world_to_screen(center_x as f32, center_y as f32);
+        let origin = vec3(origin_x, origin_y, 0.0);
+
+        let mut batch = Mesh {
+            vertices: Vec::with_capacity(TILES_PER_BATCH * TILE_VERTICES),
+            indices: Vec::with_capacity(TILES_PER_BATCH * TILE_INDICES),
+            texture: None,
+        };
+        for (&(world_x, world_y), block) in mesh.tiles.iter().zip(mesh.vertices.chunks(TILE_VERTICES)) {
+            // Skip if off screen (the first vertex is the tile's top corner)
+            let top = block[0].position + origin;
+            let (screen_x, screen_y) = (top.x, top.y + TILE_HEIGHT / 2.0);
+            if screen_x < -TILE_WIDTH || screen_x > screen_w + TILE_WIDTH
+                || screen_y < -TILE_HEIGHT || screen_y > screen_h + TILE_HEIGHT
+            {
+                continue;
+            }
+
+            // Only the diamond is lit; grid lines keep their faint fixed tint
+            let light = vision_light(world_x as f32 - player_x, world_y as f32 - player_y);
+            let base = batch.vertices.len() as u16;
+            batch.vertices.extend(block.iter().enumerate().map(|(i, vertex)| {
+                let mut vertex = *vertex;
+                vertex.position += origin;
+                if i < 4 {
+                    for channel in &mut vertex.color[..3] {
+                        *channel = (*channel as f32 * light) as u8;
+                    }
+                }
+                vertex
+            }));
+            batch.indices.extend(TILE_INDEX_PATTERN.iter().map(|&i| base + i));
+
+            if batch.indices.len() + TILE_INDICES > TILES_PER_BATCH * TILE_INDICES {
+                draw_mesh(&batch);
+                batch.vertices.clear();
+                batch.indices.clear();
+            }
+        }
+        if !batch.indices.is_empty() {
+            draw_mesh(&batch);
+        }
+    }
+
+    /// Debug overlay: outline each tile near the player and label it with its coordinate and
+    /// terrain letter. Only on-screen tiles within `DEBUG_GRID_RADIUS` are sampled.
+    pub fn draw_debug_grid(&self, camera: &GameCamera, player_x: f32, player_y: f32) {
+        let (screen_w, screen_h) = (screen_width(), screen_height());
+        let center_x = player_x.round() as i32;
+        let center_y = player_y.round() as i32;
+        let outline = Color::new(1.0, 1.0, 1.0, 0.25);
+
+        for y in (center_y - DEBUG_GRID_RADIUS)..=(center_y + DEBUG_GRID_RADIUS) {
+            for x in (center_x - DEBUG_GRID_RADIUS)..=(center_x + DEBUG_GRID_RADIUS) {
+                let (sx, sy) = camera.world_to_screen(x as f32, y as f32);
+                if sx < -TILE_WIDTH || sx > screen_w + TILE_WIDTH || sy < -TILE_HEIGHT || sy > screen_h + TILE_HEIGHT {
                     continue;
                 }
 
-                // Get blended terrain color
-                let color = self.get_blended_color(world_x as f32, world_y as f32);
+                draw_tile_outline(sx, sy, 1.0, outline);
 
-                // Draw isometric diamond tile
-                draw_isometric_tile(screen_x, screen_y, color);
+                let letter = match self.get_terrain_at(x as f32, y as f32) {
+                    Terrain::Grass => "G",
+                    Terrain::Desert => "D",
+                    Terrain::Snow => "S",
+                };
+                let label = format!("{},{} {}", x, y, letter);
+                let dims = measure_text(&label, None, 11, 1.0);
+                draw_text(&label, sx - dims.width / 2.0, sy + 4.0, 11.0, BLACK);
             }
         }
+    }
+
+    /// Collect decorations on visible tiles so they can be depth-sorted with entities
+    pub fn visible_decorations(&self, camera: &GameCamera) -> Vec<(f32, f32, Decoration)> {
+        let screen_w = screen_width();
+        let screen_h = screen_height();
 
-        // Draw decorations (second pass for proper layering)
-        for dy in -tiles_y..=tiles_y {
-            for dx in -tiles_x..=tiles_x {
+        let radius = visible_tile_radius();
+
+        let cam_tile_x = camera.x as i32;
+        let cam_tile_y = camera.y as i32;
+
+        let mut decorations = Vec::new();
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
                 let world_x = cam_tile_x + dx;
                 let world_y = cam_tile_y + dy;
 
@@ -217,36 +591,79 @@ impl World {
                     continue;
                 }
 
+                // Positions include the jitter, so depth sorting uses where the decoration is drawn
                 if let Some(decoration) = self.get_decoration_at(world_x, world_y) {
-                    decoration.draw(screen_x, screen_y);
+                    let (offset_x, offset_y) = self.decoration_offset(world_x, world_y);
+                    decorations.push((world_x as f32 + offset_x, world_y as f32 + offset_y, decoration));
                 }
             }
         }
+        decorations
+    }
+}
+
+/// Tiles from the camera to the farthest screen corner, plus a margin for tall decorations.
+/// A screen corner is offset along both world axes at once, so one radius covers both.
+fn visible_tile_radius() -> i32 {
+    let half_w = screen_width() / 2.0 / (TILE_WIDTH / 2.0);
+    let half_h = screen_height() / 2.0 / (TILE_HEIGHT / 2.0);
+    ((half_w + half_h) / 2.0).ceil() as i32 + 4
+}
+
+/// Tile whose diamond contains a screen point; tiles are centered on whole coordinates,
+/// so each one covers ±0.5 on both world axes and rounding each axis picks the diamond drawn there
+pub fn tile_at_screen(camera: &GameCamera, screen_x: f32, screen_y: f32) -> (i32, i32) {
+    let (world_x, world_y) = camera.screen_to_world(screen_x, screen_y);
+    (world_x.round() as i32, world_y.round() as i32)
+}
+
+/// Outline the tile under the mouse cursor
+pub fn draw_hover_tile(camera: &GameCamera) {
+    let (mouse_x, mouse_y) = mouse_position();
+    let (tile_x, tile_y) = tile_at_screen(camera, mouse_x, mouse_y);
+    let (sx, sy) = camera.world_to_screen(tile_x as f32, tile_y as f32);
+    draw_tile_outline(sx, sy, 2.0, Color::new(1.0, 1.0, 0.8, 0.6));
+}
+
+fn draw_tile_outline(x: f32, y: f32, thickness: f32, color: Color) {
+    let (hw, hh) = (TILE_WIDTH / 2.0, TILE_HEIGHT / 2.0);
+    let (top, right) = (Vec2::new(x, y - hh), Vec2::new(x + hw, y));
+    let (bottom, left) = (Vec2::new(x, y + hh), Vec2::new(x - hw, y));
+    for (a, b) in [(top, right), (right, bottom), (bottom, left), (left, top)] {
+        draw_line(a.x, a.y, b.x, b.y, thickness, color);
     }
 }
 
-fn draw_isometric_tile(x: f32, y: f32, color: Color) {
+/// Triangles of one tile block: the diamond as two halves, then each grid line as a quad
+const TILE_INDEX_PATTERN: [u16; TILE_INDICES] = [0, 1, 2, 0, 3, 2, 4, 5, 6, 4, 6, 7, 8, 9, 10, 8, 10, 11];
+
+/// Append one tile's vertices (top, left, bottom, right, then the two grid-line quads)
+fn push_tile_vertices(vertices: &mut Vec<Vertex>, x: f32, y: f32, color: Color) {
     let hw = TILE_WIDTH / 2.0;
     let hh = TILE_HEIGHT / 2.0;
+    let corner = |cx: f32, cy: f32, color: Color| Vertex::new(cx, cy, 0.0, 0.0, 0.0, color);
 
-    // Draw diamond shape
-    draw_triangle(
-        Vec2::new(x, y - hh),      // Top
-        Vec2::new(x - hw, y),      // Left
-        Vec2::new(x, y + hh),      // Bottom
-        color,
-    );
-    draw_triangle(
-        Vec2::new(x, y - hh),      // Top
-        Vec2::new(x + hw, y),      // Right
-        Vec2::new(x, y + hh),      // Bottom
-        color,
-    );
-
-    // Subtle grid lines
+    // Diamond shape
+    vertices.extend([
+        corner(x, y - hh, color),  // Top
+        corner(x - hw, y, color),  // Left
+        corner(x, y + hh, color),  // Bottom
+        corner(x + hw, y, color),  // Right
+    ]);
+
+    // Subtle grid lines along the two upper edges, one pixel thick
     let line_color = Color::from_rgba(0, 0, 0, 20);
-    draw_line(x, y - hh, x - hw, y, 1.0, line_color);
-    draw_line(x, y - hh, x + hw, y, 1.0, line_color);
+    for end_x in [x - hw, x + hw] {
+        let (dx, dy) = (end_x - x, hh);
+        let len = (dx * dx + dy * dy).sqrt();
+        let (nx, ny) = (-dy / len * 0.5, dx / len * 0.5);
+        vertices.extend([
+            corner(x + nx, y - hh + ny, line_color),
+            corner(end_x + nx, y + ny, line_color),
+            corner(end_x - nx, y - ny, line_color),
+            corner(x - nx, y - hh - ny, line_color),
+        ]);
+    }
 }
 
 fn lerp_color(a: Color, b: Color, t: f32) -> Color {